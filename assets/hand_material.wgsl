@@ -0,0 +1,57 @@
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::view,
+}
+
+struct HandMaterial {
+    color: vec4<f32>,
+    rim_color: vec4<f32>,
+    glow_color: vec4<f32>,
+    // xyz is the fingertip position, w its glow
+    fingertips: array<vec4<f32>, 5>,
+    rim_power: f32,
+    glow_radius: f32,
+};
+
+@group(2) @binding(0)
+var<uniform> material: HandMaterial;
+@group(2) @binding(1)
+var gradient_texture: texture_2d<f32>;
+@group(2) @binding(2)
+var gradient_sampler: sampler;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = material.color;
+#ifdef VERTEX_UVS_A
+    // Wrist to fingertip fade
+    color *= textureSample(gradient_texture, gradient_sampler, in.uv);
+#endif
+    // Fades the whole hand out when tracking is lost
+    var fade = 1.0;
+#ifdef VERTEX_COLORS
+    color *= in.color;
+    fade = in.color.a;
+#endif
+
+    // Fresnel rim
+    let view_dir = normalize(view.world_position.xyz - in.world_position.xyz);
+    let facing = saturate(dot(normalize(in.world_normal), view_dir));
+    let rim = pow(1.0 - facing, material.rim_power) * material.rim_color.a;
+    color = vec4(color.rgb + material.rim_color.rgb * rim, color.a);
+
+    // Fingertip glow
+    var glow = 0.0;
+    for (var i = 0u; i < 5u; i++) {
+        let tip = material.fingertips[i];
+        let falloff = 1.0 - saturate(distance(in.world_position.xyz, tip.xyz) / material.glow_radius);
+        glow = max(glow, tip.w * falloff);
+    }
+    glow *= fade;
+    color = vec4(
+        color.rgb + material.glow_color.rgb * glow,
+        max(color.a, glow * material.glow_color.a),
+    );
+
+    return color;
+}
@@ -1,23 +1,59 @@
-use bevy::asset::weak_handle;
+use bevy::asset::{load_internal_asset, weak_handle};
 use bevy::math::{Quat, Vec3};
+use bevy::platform::collections::HashMap;
+use bevy::platform::time::Instant;
 use bevy::prelude::*;
-use bevy::render::mesh::{Indices, Mesh, MeshAabb};
+use bevy::render::mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues};
 use bevy::render::primitives::Aabb;
 use bevy::render::render_asset::RenderAssetUsages;
 use bevy::render::render_resource::{Extent3d, PrimitiveTopology, TextureDimension, TextureFormat};
+use bevy::render::view::RenderLayers;
 
-use bevy_mod_xr::hands::{HAND_JOINT_COUNT, HandBone, XrHandBoneEntities, XrHandBoneRadius};
-use bevy_mod_xr::session::XrSessionCreated;
-use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+use aim::{HandAimRay, HandAimSettings, draw_hand_aim_rays, update_hand_aim_rays};
+use bevy::ecs::query::{QueryData, QueryFilter, QueryItem, ROQueryItem};
+use bevy::ecs::system::SystemParam;
+use bevy_mod_xr::hands::{HAND_JOINT_COUNT, HandBone, LeftHand, RightHand, XrHandBoneEntities};
+use bevy_mod_xr::session::{XrPreDestroySession, XrSessionCreated};
+use colliders::{HandColliders, update_hand_colliders};
+use diagnostics::HandMeshStats;
+use filter::filter_hand_joints;
+use gestures::{HandGestureEvent, HandGestureSettings, HandGestureState, update_hand_gestures};
+use grab::{GrabEvent, HandGrabState, release_removed_hand, update_grabs};
+use material::{HAND_SHADER_HANDLE, HandFingertipGlow, HandMaterial, update_hand_material};
+use poke::{HandPokeSettings, HandPokeState, PokeEvent, update_hand_pokes};
+use skinned::{
+    SkinnedHand, bind_skinned_hand_bones, despawn_skinned_hand, spawn_skinned_hand,
+    update_skinned_hand_render_layers, update_skinned_hands,
+};
+use source::{HandJointData, HandJointSourcePlugin};
 use std::f32::consts::{PI, SQRT_2};
+use tracking::{HandTrackingStatus, update_hand_tracking_status};
+
+pub mod aim;
+pub mod colliders;
+pub mod controller;
+pub mod debug;
+pub mod diagnostics;
+pub mod export;
+pub mod filter;
+pub mod gestures;
+pub mod grab;
+pub mod material;
+pub mod menu;
+pub mod network;
+pub mod poke;
+pub mod recording;
+pub mod simulator;
+pub mod skinned;
+pub mod source;
+pub mod tracking;
 
-const RING_COUNT: usize = SINCOS_ANGLES.len();
 pub const GRADIENT_TEXTURE_HANDLE: Handle<Image> =
     weak_handle!("14ca4cdb-3d9f-4338-af99-3c0554806440");
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
-enum Finger {
+pub enum Finger {
     Thumb = 0,
     Index = 1,
     Middle = 2,
@@ -25,29 +61,29 @@ enum Finger {
     Little = 4,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
-enum FingerJoint {
+pub enum FingerJoint {
     Metacarpal = 0,
-    Proximial = 1,
+    Proximal = 1,
     Intermediate = 2,
     Distal = 3,
     Tip = 4,
 }
 impl FingerJoint {
-    const ALL: [Self; 5] = [
+    pub const ALL: [Self; 5] = [
         Self::Metacarpal,
-        Self::Proximial,
+        Self::Proximal,
         Self::Intermediate,
         Self::Distal,
         Self::Tip,
     ];
-    const NUM: usize = Self::ALL.len();
-    const fn previous_in_chain(&self) -> FingerJoint {
+    pub const NUM: usize = Self::ALL.len();
+    pub const fn previous_in_chain(&self) -> FingerJoint {
         match self {
             FingerJoint::Metacarpal => FingerJoint::Metacarpal,
-            FingerJoint::Proximial => FingerJoint::Metacarpal,
-            FingerJoint::Intermediate => FingerJoint::Proximial,
+            FingerJoint::Proximal => FingerJoint::Metacarpal,
+            FingerJoint::Intermediate => FingerJoint::Proximal,
             FingerJoint::Distal => FingerJoint::Intermediate,
             FingerJoint::Tip => FingerJoint::Distal,
         }
@@ -55,18 +91,18 @@ impl FingerJoint {
 }
 
 impl Finger {
-    const ALL: [Finger; 5] = [
+    pub const ALL: [Finger; 5] = [
         Finger::Thumb,
         Finger::Index,
         Finger::Middle,
         Finger::Ring,
         Finger::Little,
     ];
-    const NUM: usize = Finger::ALL.len();
-    const fn hand_bone(&self, joint: &FingerJoint) -> HandBone {
+    pub const NUM: usize = Finger::ALL.len();
+    pub const fn hand_bone(&self, joint: &FingerJoint) -> HandBone {
         match (self, joint) {
             (Finger::Thumb, FingerJoint::Metacarpal) => HandBone::Wrist,
-            (Finger::Thumb, FingerJoint::Proximial) => HandBone::ThumbMetacarpal,
+            (Finger::Thumb, FingerJoint::Proximal) => HandBone::ThumbMetacarpal,
             (Finger::Thumb, FingerJoint::Intermediate) => HandBone::ThumbProximal,
             (Finger::Thumb, FingerJoint::Distal) => HandBone::ThumbDistal,
             (Finger::Thumb, FingerJoint::Tip) => HandBone::ThumbTip,
@@ -78,96 +114,302 @@ impl Finger {
     }
 }
 
-#[derive(Clone, Copy)]
-struct HandJoint {
-    position: Vec3,
-    orientation: Quat,
-    radius: f32,
+/// Which hand an entity is.
+///
+/// Inserted on every hand entity with a [`LeftHand`] or [`RightHand`] marker.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Handed {
+    Left,
+    Right,
 }
 
-const SINCOS_ANGLES: [f32; 7] = [162.0, 90.0, 18.0, 18.0, 306.0, 234.0, 162.0];
-const SINCOS_NORM_ANGLES: [f32; 7] = [126.0, 90.0, 54.0, 18.0, 306.0, 234.0, 162.0];
+impl Handed {
+    pub const BOTH: [Handed; 2] = [Handed::Left, Handed::Right];
 
-#[derive(Clone, Copy, Component, Debug)]
-pub struct DisplayHandMesh;
+    pub const fn other(self) -> Self {
+        match self {
+            Handed::Left => Handed::Right,
+            Handed::Right => Handed::Left,
+        }
+    }
+
+    /// -1 for the left hand and 1 for the right, to mirror offsets along X.
+    pub const fn side(self) -> f32 {
+        match self {
+            Handed::Left => -1.0,
+            Handed::Right => 1.0,
+        }
+    }
+}
+
+fn insert_handed<C: Component>(
+    trigger: Trigger<OnAdd, C>,
+    hands: Query<(Has<LeftHand>, Has<RightHand>), With<XrHandBoneEntities>>,
+    mut commands: Commands,
+) {
+    let handed = match hands.get(trigger.target()) {
+        Ok((true, _)) => Handed::Left,
+        Ok((_, true)) => Handed::Right,
+        _ => return,
+    };
+    commands.entity(trigger.target()).insert(handed);
+}
 
-struct SkHandFinger(Finger);
+/// Queries the hands by [`Handed`], for systems that care about one hand in particular.
+#[derive(SystemParam)]
+pub struct Hands<'w, 's, D: QueryData + 'static, F: QueryFilter + 'static = ()> {
+    hands: Query<'w, 's, (Entity, &'static Handed, D), F>,
+}
+
+impl<D: QueryData, F: QueryFilter> Hands<'_, '_, D, F> {
+    pub fn entity(&self, handed: Handed) -> Option<Entity> {
+        self.hands
+            .iter()
+            .find(|(_, h, _)| **h == handed)
+            .map(|(e, _, _)| e)
+    }
+
+    pub fn get(&self, handed: Handed) -> Option<ROQueryItem<'_, D>> {
+        self.hands
+            .iter()
+            .find(|(_, h, _)| **h == handed)
+            .map(|(_, _, item)| item)
+    }
+
+    pub fn get_mut(&mut self, handed: Handed) -> Option<QueryItem<'_, D>> {
+        self.hands
+            .iter_mut()
+            .find(|(_, h, _)| **h == handed)
+            .map(|(_, _, item)| item)
+    }
 
-impl SkHandFinger {
-    const fn vertex_count() -> usize {
-        let base_verts = RING_COUNT * FingerJoint::NUM;
-        let extra_tips = RING_COUNT;
-        let end_extra = 1;
-        base_verts + extra_tips + end_extra
+    pub fn left(&self) -> Option<ROQueryItem<'_, D>> {
+        self.get(Handed::Left)
     }
-    const fn start_vert(&self, index: usize) -> usize {
-        index * Self::vertex_count()
+
+    pub fn right(&self) -> Option<ROQueryItem<'_, D>> {
+        self.get(Handed::Right)
     }
-    const fn end_vert(&self, index: usize) -> usize {
-        self.start_vert(index) + Self::vertex_count() - 1
+
+    pub fn iter(&self) -> impl Iterator<Item = (Handed, ROQueryItem<'_, D>)> {
+        self.hands.iter().map(|(_, h, item)| (*h, item))
+    }
+}
+
+/// World space pose and radius of a single hand joint, indexed by [`HandBone`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandJoint {
+    pub position: Vec3,
+    pub orientation: Quat,
+    pub radius: f32,
+}
+
+impl Default for HandJoint {
+    fn default() -> Self {
+        Self {
+            position: Vec3::ZERO,
+            orientation: Quat::IDENTITY,
+            radius: 0.0,
+        }
     }
-    fn indices(&self, index: usize) -> Vec<u16> {
-        let end_vert = self.end_vert(index) as u16 - 7;
-        let start_vert = self.start_vert(index) as u16;
+}
+
+bitflags::bitflags! {
+    /// Fingers whose geometry should be generated, usually the ones with a tracked tip.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct TrackedFingers: u8 {
+        const THUMB  = (1 << 0);
+        const INDEX  = (1 << 1);
+        const MIDDLE = (1 << 2);
+        const RING   = (1 << 3);
+        const LITTLE = (1 << 4);
+    }
+}
+
+impl From<Finger> for TrackedFingers {
+    fn from(finger: Finger) -> Self {
+        TrackedFingers::from_bits_truncate(1 << finger as u8)
+    }
+}
+
+/// The latest joints of a hand, gathered once per frame for the mesh and interaction systems.
+#[derive(Component, Clone, Debug)]
+pub struct HandJoints {
+    /// Untracked joints keep their last tracked pose.
+    pub joints: [HandJoint; HAND_JOINT_COUNT],
+    /// Whether both the position and rotation of each joint are tracked.
+    pub tracked: [bool; HAND_JOINT_COUNT],
+}
+
+impl Default for HandJoints {
+    fn default() -> Self {
+        Self {
+            joints: [HandJoint::default(); HAND_JOINT_COUNT],
+            tracked: [false; HAND_JOINT_COUNT],
+        }
+    }
+}
+
+impl HandJoints {
+    pub fn get(&self, bone: HandBone) -> &HandJoint {
+        &self.joints[bone as usize]
+    }
+
+    pub fn is_tracked(&self, bone: HandBone) -> bool {
+        self.tracked[bone as usize]
+    }
+
+    /// Fingers with a tracked tip.
+    pub fn tracked_fingers(&self) -> TrackedFingers {
+        let mut tracked = TrackedFingers::empty();
+        for finger in Finger::ALL {
+            if self.is_tracked(finger.hand_bone(&FingerJoint::Tip)) {
+                tracked |= finger.into();
+            }
+        }
+        tracked
+    }
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HandSet {
+    /// Fills [`HandJoints`] from each hand's [`HandJointSource`](source::HandJointSource).
+    Joints,
+    /// Derives interaction state like [`HandGestureState`] and [`HandAimRay`] from [`HandJoints`].
+    Interaction,
+    /// Updates the hand meshes.
+    Visuals,
+}
+
+/// Detail of the generated hand mesh, as the number of segments around each finger.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HandMeshLod {
+    /// Triangular fingers, for mobile headsets.
+    Low,
+    /// StereoKit's pentagonal fingers.
+    #[default]
+    Medium,
+    /// Round fingers, for close-up captures.
+    High,
+    /// Any number of segments, at least 3.
+    Custom(u16),
+}
+
+impl HandMeshLod {
+    pub fn ring_segments(self) -> usize {
+        match self {
+            HandMeshLod::Low => 3,
+            HandMeshLod::Medium => 5,
+            HandMeshLod::High => 12,
+            HandMeshLod::Custom(segments) => (segments as usize).max(3),
+        }
+    }
+}
+
+/// Unit offsets of the vertices around each ring of a finger.
+///
+/// Rings are split into a flatter top arc from 162° to 18° and a bottom arc back around to 162°.
+/// Both arcs have their own vertices at the seams, so the top and bottom are tinted separately.
+#[derive(Clone, Debug)]
+struct HandMeshRing {
+    positions: Vec<Vec2>,
+    normals: Vec<Vec2>,
+    /// Segments in the top arc, the bottom arc starts at the vertex after it.
+    top_segments: usize,
+}
+
+impl HandMeshRing {
+    fn new(lod: HandMeshLod) -> Self {
+        let segments = lod.ring_segments();
+        let top_segments = ((segments as f32 * 0.4).round() as usize).clamp(1, segments - 2);
+        let bottom_segments = segments - top_segments;
+        let top = (0..=top_segments).map(|i| 162.0 - 144.0 * i as f32 / top_segments as f32);
+        let bottom =
+            (0..=bottom_segments).map(|i| 18.0 - 216.0 * i as f32 / bottom_segments as f32);
+
+        let mut positions = Vec::with_capacity(segments + 2);
+        let mut normals = Vec::with_capacity(segments + 2);
+        for angle in top {
+            positions.push(Vec2::from_angle(angle.to_radians()));
+            // Bent halfway towards straight up, flattening the back of the fingers
+            normals.push(Vec2::from_angle(((angle + 90.0) * 0.5).to_radians()));
+        }
+        for angle in bottom {
+            positions.push(Vec2::from_angle(angle.to_radians()));
+            normals.push(Vec2::from_angle(angle.to_radians()));
+        }
+        Self {
+            positions,
+            normals,
+            top_segments,
+        }
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn is_top(&self, vertex: usize) -> bool {
+        vertex <= self.top_segments
+    }
+
+    /// A ring per joint, one rounding off the tip and the tip itself.
+    fn finger_vertex_count(&self) -> usize {
+        self.vertex_count() * (FingerJoint::NUM + 1) + 1
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug)]
+pub struct DisplayHandMesh;
+
+struct SkHandFinger<'a> {
+    finger: Finger,
+    ring: &'a HandMeshRing,
+}
+
+impl SkHandFinger<'_> {
+    fn indices(&self, index: usize) -> Vec<u32> {
+        let ring = self.ring.vertex_count() as u32;
+        let top = self.ring.top_segments as u32;
+        let start = (index * self.ring.finger_vertex_count()) as u32;
+        let last_ring = start + ring * FingerJoint::NUM as u32;
+        let tip = last_ring + ring;
         let mut indices = Vec::new();
 
-        // Start cap indices
-        #[expect(clippy::identity_op)]
-        indices.extend_from_slice(&[
-            (start_vert + 2),
-            (start_vert + 1),
-            (start_vert + 0),
-            (start_vert + 4),
-            (start_vert + 3),
-            (start_vert + 6),
-            (start_vert + 5),
-            (start_vert + 4),
-            (start_vert + 6),
-        ]);
-
-        // Tube faces indices
-        for joint in 0..FingerJoint::NUM as u16 {
-            for ring in 0..RING_COUNT as u16 {
-                // huh?
-                // if ring == 2 {
-                //     continue;
-                // }
-                let curr1 = start_vert + (joint * RING_COUNT as u16 + ring);
-                let next1 = start_vert + (joint + 1) * RING_COUNT as u16 + ring;
-                let curr2 = start_vert + (joint * RING_COUNT as u16 + (ring + 1));
-                let next2 = start_vert + (joint + 1) * RING_COUNT as u16 + (ring + 1);
-
-                if ring == RING_COUNT as u16 - 1 {
-                    continue;
-                }
-                indices.extend_from_slice(&[next2, next1, curr1, curr2, next2, curr1]);
+        // Start cap, fanning out the top and bottom arcs separately
+        for v in 1..top {
+            indices.extend_from_slice(&[start + v + 1, start + v, start]);
+        }
+        for v in top + 1..ring - 2 {
+            indices.extend_from_slice(&[start + v + 1, start + v, start + ring - 1]);
+        }
+
+        // Tube faces, skipping the seams between the arcs as they have no width
+        for joint in 0..FingerJoint::NUM as u32 {
+            let curr = start + joint * ring;
+            let next = curr + ring;
+            for v in (0..ring - 1).filter(|v| *v != top) {
+                indices.extend_from_slice(&[
+                    next + v + 1,
+                    next + v,
+                    curr + v,
+                    curr + v + 1,
+                    next + v + 1,
+                    curr + v,
+                ]);
             }
         }
 
-        // End cap indices
-        #[expect(clippy::identity_op)]
-        indices.extend_from_slice(&[
-            (end_vert + 0),
-            (end_vert + 1),
-            (end_vert + 7),
-            (end_vert + 1),
-            (end_vert + 2),
-            (end_vert + 7),
-            (end_vert + 3),
-            (end_vert + 4),
-            (end_vert + 7),
-            (end_vert + 4),
-            (end_vert + 5),
-            (end_vert + 7),
-            (end_vert + 5),
-            (end_vert + 6),
-            (end_vert + 7),
-        ]);
+        // End cap, fanning out to the tip
+        for v in (0..ring - 1).filter(|v| *v != top) {
+            indices.extend_from_slice(&[last_ring + v, last_ring + v + 1, tip]);
+        }
 
         indices
     }
 
-    fn gen_uvs(&self, finger: Finger) -> Vec<[f32; 2]> {
+    fn gen_uvs(&self) -> Vec<[f32; 2]> {
         const TEXTURE_COORDINATES_Y: [f32; 6] = [
             1f32,
             1f32 - 0.44f32,
@@ -176,21 +418,19 @@ impl SkHandFinger {
             1f32 - 0.96f32,
             1f32 - 0.99f32,
         ];
+        let finger = self.finger;
         let x = (finger as u8 as f32 / Finger::NUM as f32) + (0.5 / Finger::NUM as f32);
-        let mut uvs = Vec::new();
+        let mut uvs = Vec::with_capacity(self.ring.finger_vertex_count());
         for joint in FingerJoint::ALL {
             let y = match finger {
                 Finger::Thumb => TEXTURE_COORDINATES_Y[joint.previous_in_chain() as usize],
                 _ => TEXTURE_COORDINATES_Y[joint as usize],
             };
-            // Push colors for each vertex
-            // for _v in 0..RING_COUNT {
-            for _ in 0..RING_COUNT {
+            for _ in 0..self.ring.vertex_count() {
                 uvs.push([x, y]);
             }
-            // }
             if matches!(joint, FingerJoint::Tip) {
-                for _ in 0..RING_COUNT {
+                for _ in 0..self.ring.vertex_count() {
                     uvs.push([x, y]);
                 }
             }
@@ -200,51 +440,44 @@ impl SkHandFinger {
         uvs
     }
 
-    fn gen_vertex_colors(&self) -> Vec<[f32; 4]> {
-        fn get_color(joint: FingerJoint) -> [f32; 4] {
-            let factor = (joint as usize as f32) / (FingerJoint::NUM as f32 - 1.0);
-            get_gradient_color(factor).map(|v| v as f32 / u8::MAX as f32)
-        }
-        let mut colors = Vec::new();
+    fn gen_vertex_colors(&self, settings: &HandVisualSettings) -> Vec<[f32; 4]> {
+        let top = settings.top_tint.to_linear().to_f32_array();
+        let bottom = settings.bottom_tint.to_linear().to_f32_array();
+        let ring = (0..self.ring.vertex_count()).map(|v| match self.ring.is_top(v) {
+            true => top,
+            false => bottom,
+        });
+        let mut colors = Vec::with_capacity(self.ring.finger_vertex_count());
         for joint in FingerJoint::ALL {
-            // Push colors for each vertex
-            // for _v in 0..RING_COUNT {
-            for v in 0..RING_COUNT {
-                if v < 3 {
-                    colors.push([1.0, 1.0, 1.0, 1.0]);
-                } else {
-                    colors.push([0.784, 0.784, 0.784, 1.0]); // Light gray (200/255)
-                }
-            }
-            // }
+            colors.extend(ring.clone());
             if matches!(joint, FingerJoint::Tip) {
-                for v in 0..RING_COUNT {
-                    if v < 3 {
-                        colors.push([1.0, 1.0, 1.0, 1.0]);
-                    } else {
-                        colors.push([0.784, 0.784, 0.784, 1.0]); // Light gray (200/255)
-                    }
-                }
+                colors.extend(ring.clone());
             }
         }
         // Extra vertex color
-        colors.push(get_color(FingerJoint::Tip));
+        colors.push(
+            settings
+                .gradient_color(1.0)
+                .map(|v| v as f32 / u8::MAX as f32),
+        );
         colors
     }
 
     fn gen_vertex_positions_and_normals(
         &self,
         data: &[HandJoint; HAND_JOINT_COUNT],
-    ) -> (Vec<[f32; 3]>, Vec<[f32; 3]>) {
-        let mut positions = Vec::new();
-        let mut normals = Vec::new();
-        let (sincos, sincos_norm) = gen_sincos_and_sincos_norm();
-        let tip = data[self.0.hand_bone(&FingerJoint::Tip) as usize];
+        settings: &HandVisualSettings,
+        positions: &mut Vec<[f32; 3]>,
+        normals: &mut Vec<[f32; 3]>,
+    ) {
+        let finger = self.finger;
+        let ring = self.ring;
+        let tip = data[finger.hand_bone(&FingerJoint::Tip) as usize];
         let tip_fwd = tip.orientation * -Vec3::Z;
         let tip_up = tip.orientation * Vec3::Y;
         for joint in FingerJoint::ALL {
-            let pose_prev = data[self.0.hand_bone(&joint.previous_in_chain()) as usize];
-            let pose = data[self.0.hand_bone(&joint) as usize];
+            let pose_prev = data[finger.hand_bone(&joint.previous_in_chain()) as usize];
+            let pose = data[finger.hand_bone(&joint) as usize];
             let orientation = pose_prev.orientation.slerp(pose.orientation, 0.5);
 
             // Scaling offset to preserve volume
@@ -252,8 +485,8 @@ impl SkHandFinger {
             if (!matches!(
                 joint,
                 FingerJoint::Tip | FingerJoint::Distal | FingerJoint::Metacarpal
-            )) && ((!matches!(self.0, Finger::Thumb))
-                || !matches!(joint, FingerJoint::Metacarpal | FingerJoint::Proximial))
+            )) && ((!matches!(finger, Finger::Thumb))
+                || !matches!(joint, FingerJoint::Metacarpal | FingerJoint::Proximal))
             {
                 let fwd_a = pose_prev.orientation * -Vec3::Z;
                 let fwd_b = pose.orientation * -Vec3::Z;
@@ -268,16 +501,16 @@ impl SkHandFinger {
 
             // Scale adjustment
             let mut scale = pose.radius;
-            if matches!(self.0, Finger::Thumb)
-                && matches!(joint, FingerJoint::Metacarpal | FingerJoint::Proximial)
+            if matches!(finger, Finger::Thumb)
+                && matches!(joint, FingerJoint::Metacarpal | FingerJoint::Proximal)
             {
-                scale *= 0.5;
+                scale *= settings.thumb_scale[joint as usize];
             }
 
             // Create ring of vertices
-            for i in 0..RING_COUNT {
-                let norm = (up * sincos_norm[i].y + right * sincos_norm[i].x) * SQRT_2;
-                let pos = pose.position + (up * sincos[i].y + right * sincos[i].x) * scale;
+            for (offset, normal) in ring.positions.iter().zip(&ring.normals) {
+                let norm = (up * normal.y + right * normal.x) * SQRT_2;
+                let pos = pose.position + (up * offset.y + right * offset.x) * scale;
 
                 positions.push([pos.x, pos.y, pos.z]);
                 normals.push([norm.x, norm.y, norm.z]);
@@ -285,13 +518,13 @@ impl SkHandFinger {
 
             // Blunt the fingertip
             if matches!(joint, FingerJoint::Tip) {
-                scale *= 0.75;
-                for i in 0..RING_COUNT {
-                    let at = pose.position + tip_fwd * pose.radius * 0.65;
-                    let norm = (up * sincos_norm[i].y + right * sincos_norm[i].x) * SQRT_2;
+                scale *= settings.tip_blunt_scale;
+                for (offset, normal) in ring.positions.iter().zip(&ring.normals) {
+                    let at = pose.position + tip_fwd * pose.radius * settings.tip_blunt_offset;
+                    let norm = (up * normal.y + right * normal.x) * SQRT_2;
                     let pos = at
-                        + (up * sincos[i].y + right * sincos[i].x) * scale
-                        + tip_up * pose.radius * 0.25;
+                        + (up * offset.y + right * offset.x) * scale
+                        + tip_up * pose.radius * settings.tip_blunt_lift;
 
                     positions.push([pos.x, pos.y, pos.z]);
                     normals.push([norm.x, norm.y, norm.z]);
@@ -304,149 +537,615 @@ impl SkHandFinger {
 
         positions.push([pos.x, pos.y, pos.z]);
         normals.push([norm.x, norm.y, norm.z]);
-        (positions, normals)
     }
 }
 
-// Function to generate sincos and sincos_norm arrays
-fn gen_sincos_and_sincos_norm() -> (
-    [Vec3; SINCOS_ANGLES.len()],
-    [Vec3; SINCOS_NORM_ANGLES.len()],
+/// Fingers in the order their geometry is laid out in the hand mesh.
+const MESH_FINGER_ORDER: [Finger; Finger::NUM] = [
+    Finger::Little,
+    Finger::Ring,
+    Finger::Middle,
+    Finger::Index,
+    Finger::Thumb,
+];
+
+/// The parts of the hand mesh that only depend on which fingers are tracked and the level of detail.
+#[derive(Clone, Debug)]
+pub struct HandMeshTopology {
+    pub tracked: TrackedFingers,
+    pub lod: HandMeshLod,
+    /// `u16` indices, unless the mesh has too many vertices for them.
+    pub indices: Indices,
+    pub uvs: Vec<[f32; 2]>,
+    ring: HandMeshRing,
+}
+
+impl HandMeshTopology {
+    pub fn new(tracked: TrackedFingers, lod: HandMeshLod) -> Self {
+        let ring = HandMeshRing::new(lod);
+        let vert_count = ring.finger_vertex_count() * tracked.bits().count_ones() as usize;
+        let mut indices = Vec::new();
+        let mut uvs = Vec::with_capacity(vert_count);
+        for (i, finger) in mesh_fingers(tracked).enumerate() {
+            let f = SkHandFinger {
+                finger,
+                ring: &ring,
+            };
+            indices.extend(f.indices(i));
+            uvs.extend(f.gen_uvs());
+        }
+        let indices = match vert_count <= u16::MAX as usize + 1 {
+            true => Indices::U16(indices.into_iter().map(|i| i as u16).collect()),
+            false => Indices::U32(indices),
+        };
+        Self {
+            tracked,
+            lod,
+            indices,
+            uvs,
+            ring,
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.uvs.len()
+    }
+
+    /// Vertex colors for this topology, tinted by `settings`.
+    pub fn colors(&self, settings: &HandVisualSettings) -> Vec<[f32; 4]> {
+        let mut colors = Vec::with_capacity(self.vertex_count());
+        for finger in mesh_fingers(self.tracked) {
+            colors.extend(
+                SkHandFinger {
+                    finger,
+                    ring: &self.ring,
+                }
+                .gen_vertex_colors(settings),
+            );
+        }
+        colors
+    }
+}
+
+fn mesh_fingers(tracked: TrackedFingers) -> impl Iterator<Item = Finger> {
+    MESH_FINGER_ORDER
+        .into_iter()
+        .filter(move |finger| tracked.contains((*finger).into()))
+}
+
+/// Writes the hand mesh vertices laid out for `topology` into the buffers, reusing their
+/// allocations.
+pub fn write_hand_mesh_vertices(
+    joints: &[HandJoint; HAND_JOINT_COUNT],
+    topology: &HandMeshTopology,
+    settings: &HandVisualSettings,
+    positions: &mut Vec<[f32; 3]>,
+    normals: &mut Vec<[f32; 3]>,
 ) {
-    (
-        SINCOS_ANGLES.map(|a| Vec3::new(a.to_radians().cos(), a.to_radians().sin(), 0f32)),
-        SINCOS_NORM_ANGLES.map(|a| Vec3::new(a.to_radians().cos(), a.to_radians().sin(), 0f32)),
-    )
+    positions.clear();
+    normals.clear();
+    for finger in mesh_fingers(topology.tracked) {
+        SkHandFinger {
+            finger,
+            ring: &topology.ring,
+        }
+        .gen_vertex_positions_and_normals(joints, settings, positions, normals);
+    }
 }
 
-fn setup_hand_mesh(
-    hands: Query<Entity, With<XrHandBoneEntities>>,
+/// Builds the StereoKit style hand mesh for the given joints, skipping fingers not in `tracked`.
+pub fn build_hand_mesh(
+    joints: &[HandJoint; HAND_JOINT_COUNT],
+    tracked: TrackedFingers,
+    settings: &HandVisualSettings,
+) -> Mesh {
+    let topology = HandMeshTopology::new(tracked, settings.lod);
+    let mut positions = Vec::with_capacity(topology.vertex_count());
+    let mut normals = Vec::with_capacity(topology.vertex_count());
+    write_hand_mesh_vertices(joints, &topology, settings, &mut positions, &mut normals);
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, topology.colors(settings));
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, topology.uvs);
+    mesh.insert_indices(topology.indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh
+}
+
+/// Appearance of the generated hand meshes.
+///
+/// Used as a resource for every hand, or as a component on a hand entity to override it.
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+pub struct HandVisualSettings {
+    /// Vertex color of the back of the fingers.
+    pub top_tint: Color,
+    /// Vertex color of the palm side of the fingers.
+    pub bottom_tint: Color,
+    /// Stops of the fade from the wrist (0) to the fingertips (1), sorted by position.
+    pub gradient: Vec<(f32, Color)>,
+    /// Radius of the ring rounding off each fingertip, relative to the tip ring.
+    pub tip_blunt_scale: f32,
+    /// How far past the tip joint the rounding ring sits, relative to the tip radius.
+    pub tip_blunt_offset: f32,
+    /// How far the rounding ring is raised towards the nail, relative to the tip radius.
+    pub tip_blunt_lift: f32,
+    /// Radius multipliers for the thumb rings at the wrist and at the thumb metacarpal.
+    pub thumb_scale: [f32; 2],
+    /// Detail of the finger rings.
+    pub lod: HandMeshLod,
+    /// Fresnel rim of the default [`HandMaterial`], alpha scales its strength.
+    pub rim_color: Color,
+    /// Fingertip glow of the default [`HandMaterial`].
+    pub glow_color: Color,
+    /// Material to render the hand with instead of the default [`HandMaterial`].
+    pub material: Option<Handle<StandardMaterial>>,
+    /// Seconds an untracked finger, or the whole hand, keeps showing its last pose.
+    pub tracking_grace_period: f32,
+    /// Hides the hand regardless of tracking, usually set on one hand through a
+    /// [`HandVisualSettings`] component.
+    pub visible: bool,
+    /// Cameras that render the hand, for example to hide it from a spectator camera or only
+    /// show it in a mirror.
+    pub render_layers: RenderLayers,
+    /// Seconds to fade the hand out once its grace period is over.
+    pub fade_out_duration: f32,
+    /// Seconds to fade the hand back in when tracking resumes.
+    pub fade_in_duration: f32,
+}
+
+impl Default for HandVisualSettings {
+    fn default() -> Self {
+        Self {
+            top_tint: Color::WHITE,
+            bottom_tint: Color::linear_rgb(0.784, 0.784, 0.784),
+            gradient: vec![
+                (0.0, Color::srgba_u8(102, 102, 102, 0)),
+                (0.4, Color::srgba_u8(153, 153, 153, 0)),
+                (0.55, Color::srgba_u8(204, 204, 204, 255)),
+                (1.0, Color::srgba_u8(255, 255, 255, 255)),
+            ],
+            tip_blunt_scale: 0.75,
+            tip_blunt_offset: 0.65,
+            tip_blunt_lift: 0.25,
+            thumb_scale: [0.5, 0.5],
+            lod: HandMeshLod::Medium,
+            rim_color: HandMaterial::default().rim_color,
+            glow_color: HandMaterial::default().glow_color,
+            material: None,
+            tracking_grace_period: 0.2,
+            visible: true,
+            render_layers: RenderLayers::default(),
+            fade_out_duration: 0.15,
+            fade_in_duration: 0.1,
+        }
+    }
+}
+
+impl HandVisualSettings {
+    /// Samples the fade gradient as sRGB bytes.
+    pub fn gradient_color(&self, t: f32) -> [u8; 4] {
+        let Some(first) = self.gradient.first() else {
+            return [255; 4];
+        };
+        let mut color = first.1.to_srgba();
+        for pair in self.gradient.windows(2) {
+            let ((from_t, from), (to_t, to)) = (pair[0], pair[1]);
+            if t >= from_t {
+                let factor = ((t - from_t) / (to_t - from_t).max(f32::EPSILON)).min(1.0);
+                color = from.to_srgba().mix(&to.to_srgba(), factor);
+            }
+        }
+        color.to_u8_array()
+    }
+}
+
+/// Topologies that have been generated so far, keyed by tracked fingers and level of detail.
+#[derive(Resource, Default)]
+struct HandMeshTopologies(HashMap<(TrackedFingers, HandMeshLod), HandMeshTopology>);
+
+impl HandMeshTopologies {
+    fn get(&mut self, tracked: TrackedFingers, lod: HandMeshLod) -> &HandMeshTopology {
+        self.0
+            .entry((tracked, lod))
+            .or_insert_with(|| HandMeshTopology::new(tracked, lod))
+    }
+}
+
+/// What the hand's mesh asset was last generated for.
+#[derive(Component, Default)]
+struct HandMeshTopologyState {
+    /// The fingers the mesh has the topology for.
+    fingers: Option<TrackedFingers>,
+    /// The alpha baked into the vertex colors.
+    alpha: f32,
+    /// Vertex colors of the topology before the alpha is applied.
+    colors: Vec<[f32; 4]>,
+}
+
+/// The per-hand material used when [`HandVisualSettings::material`] is not set.
+#[derive(Component)]
+struct DefaultHandMaterial(Handle<HandMaterial>);
+
+/// Gives hands their mesh as soon as their bone entities are inserted, whenever that happens.
+fn setup_hand_mesh<S: Component>(
+    trigger: Trigger<OnInsert, S>,
+    hands: Query<(), (Without<DefaultHandMaterial>, Without<SkinnedHand>)>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+) {
+    if hands.contains(trigger.target()) {
+        insert_hand_mesh(trigger.target(), &mut commands, &mut meshes, &mut materials);
+    }
+}
+
+/// Catches hands whose bone entities outlived a previous session.
+#[expect(clippy::type_complexity)]
+fn setup_missing_hand_meshes(
+    hands: Query<
+        Entity,
+        (
+            With<XrHandBoneEntities>,
+            Without<DefaultHandMaterial>,
+            Without<SkinnedHand>,
+        ),
+    >,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
 ) {
     for e in &hands {
-        info!("creating hand");
-        let mut hand_mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
-        hand_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]]);
-        commands.entity(e).insert((
-            Mesh3d(meshes.add(hand_mesh)),
-            MeshMaterial3d(materials.add(StandardMaterial {
-                unlit: true,
-                alpha_mode: AlphaMode::Blend,
-                base_color_texture: Some(GRADIENT_TEXTURE_HANDLE),
-                ..default()
-            })),
-            Transform::from_xyz(0.0, 0.0, 0.0),
-            Aabb::default(),
-        ));
-    }
-}
-
-const fn get_gradient_color(t: f32) -> [u8; 4] {
-    if t < 0.4 {
-        let factor = t / 0.4;
-        lerp_color([102, 102, 102, 0], [153, 153, 153, 0], factor)
-    } else if t < 0.55 {
-        let factor = (t - 0.4) / 0.15;
-        lerp_color([153, 153, 153, 0], [204, 204, 204, 255], factor)
-    } else {
-        let factor = (t - 0.55) / 0.45;
-        lerp_color([204, 204, 204, 255], [255, 255, 255, 255], factor)
-    }
-}
-
-const fn lerp_color(a: [u8; 4], b: [u8; 4], t: f32) -> [u8; 4] {
-    [
-        (a[0] as f32 + (b[0] as f32 - a[0] as f32) * t) as u8,
-        (a[1] as f32 + (b[1] as f32 - a[1] as f32) * t) as u8,
-        (a[2] as f32 + (b[2] as f32 - a[2] as f32) * t) as u8,
-        (a[3] as f32 + (b[3] as f32 - a[3] as f32) * t) as u8,
-    ]
+        insert_hand_mesh(e, &mut commands, &mut meshes, &mut materials);
+    }
+}
+
+fn insert_hand_mesh(
+    e: Entity,
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<HandMaterial>,
+) {
+    info!("creating hand");
+    let mut hand_mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
+    hand_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]]);
+    let material = materials.add(HandMaterial {
+        gradient_texture: Some(GRADIENT_TEXTURE_HANDLE),
+        ..default()
+    });
+    // The hand may be getting despawned
+    commands.entity(e).try_insert((
+        Mesh3d(meshes.add(hand_mesh)),
+        MeshMaterial3d(material.clone()),
+        DefaultHandMaterial(material),
+        Transform::from_xyz(0.0, 0.0, 0.0),
+        Aabb::default(),
+        HandMeshTopologyState::default(),
+        HandMeshStats::default(),
+    ));
+}
+
+/// Swaps the procedural mesh for the model while a hand has a [`SkinnedHand`].
+fn remove_skinned_hand_mesh(
+    trigger: Trigger<OnAdd, SkinnedHand>,
+    hands: Query<(&Mesh3d, &DefaultHandMaterial)>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+) {
+    if let Ok((mesh, material)) = hands.get(trigger.target()) {
+        remove_hand_mesh(
+            trigger.target(),
+            mesh,
+            material,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
+
+fn restore_skinned_hand_mesh(
+    trigger: Trigger<OnRemove, SkinnedHand>,
+    hands: Query<(), With<HandJoints>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+) {
+    if hands.contains(trigger.target()) {
+        insert_hand_mesh(trigger.target(), &mut commands, &mut meshes, &mut materials);
+    }
+}
+
+fn cleanup_hand_mesh<S: Component>(
+    trigger: Trigger<OnRemove, S>,
+    hands: Query<(&Mesh3d, &DefaultHandMaterial)>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+) {
+    if let Ok((mesh, material)) = hands.get(trigger.target()) {
+        remove_hand_mesh(
+            trigger.target(),
+            mesh,
+            material,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
+
+/// Frees the hand mesh assets before the session, and usually the hands, go away.
+fn cleanup_hand_meshes(
+    hands: Query<(Entity, &Mesh3d, &DefaultHandMaterial), With<XrHandBoneEntities>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+) {
+    for (e, mesh, material) in &hands {
+        remove_hand_mesh(
+            e,
+            mesh,
+            material,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
+
+fn remove_hand_mesh(
+    e: Entity,
+    mesh: &Mesh3d,
+    material: &DefaultHandMaterial,
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<HandMaterial>,
+) {
+    info!("removing hand");
+    meshes.remove(&mesh.0);
+    materials.remove(&material.0);
+    // The hand may be getting despawned
+    commands.entity(e).try_remove::<(
+        Mesh3d,
+        MeshMaterial3d<HandMaterial>,
+        MeshMaterial3d<StandardMaterial>,
+        DefaultHandMaterial,
+        Aabb,
+        HandMeshTopologyState,
+        HandMeshStats,
+    )>();
 }
 
 pub struct HandPlugin;
 
 impl Plugin for HandPlugin {
     fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HAND_SHADER_HANDLE,
+            "../assets/hand_material.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_plugins(MaterialPlugin::<HandMaterial>::default());
+        app.register_type::<HandMaterial>();
+        app.init_resource::<HandVisualSettings>();
+        let gradient = create_gradient_texture(app.world().resource::<HandVisualSettings>());
         app.world_mut()
             .resource_mut::<Assets<Image>>()
-            .insert(&GRADIENT_TEXTURE_HANDLE, create_gradient_texture());
-        app.add_systems(XrSessionCreated, setup_hand_mesh);
-        app.add_systems(Update, update_hand_mesh);
+            .insert(&GRADIENT_TEXTURE_HANDLE, gradient);
+        app.init_resource::<HandMeshTopologies>();
+        app.init_resource::<HandGestureSettings>();
+        app.init_resource::<HandAimSettings>();
+        app.init_resource::<HandPokeSettings>();
+        app.add_event::<HandGestureEvent>();
+        app.add_event::<PokeEvent>();
+        app.add_event::<GrabEvent>();
+        app.add_observer(insert_handed::<XrHandBoneEntities>);
+        app.add_observer(insert_handed::<LeftHand>);
+        app.add_observer(insert_handed::<RightHand>);
+        app.register_required_components::<HandJoints, HandGestureState>();
+        app.register_required_components::<HandJoints, HandAimRay>();
+        app.register_required_components::<HandJoints, HandFingertipGlow>();
+        app.register_required_components::<HandJoints, HandTrackingStatus>();
+        app.register_required_components::<HandJoints, HandColliders>();
+        app.register_required_components::<HandJoints, HandPokeState>();
+        app.register_required_components::<HandJoints, HandGrabState>();
+        app.configure_sets(
+            Update,
+            (HandSet::Joints, HandSet::Interaction, HandSet::Visuals).chain(),
+        );
+        app.add_plugins(HandJointSourcePlugin::<XrHandBoneEntities>::default());
+        app.add_plugins(HandJointSourcePlugin::<HandJointData>::default());
+        app.add_observer(release_removed_hand);
+        app.add_observer(remove_skinned_hand_mesh);
+        app.add_observer(restore_skinned_hand_mesh);
+        app.add_observer(spawn_skinned_hand);
+        app.add_observer(despawn_skinned_hand);
+        app.add_observer(bind_skinned_hand_bones);
+        app.add_systems(XrSessionCreated, setup_missing_hand_meshes);
+        app.add_systems(XrPreDestroySession, cleanup_hand_meshes);
+        app.add_systems(Update, filter_hand_joints.in_set(HandSet::Joints));
+        app.add_systems(
+            Update,
+            (
+                update_hand_gestures,
+                update_hand_aim_rays,
+                update_hand_colliders,
+                update_hand_pokes,
+                update_grabs.after(update_hand_gestures),
+            )
+                .in_set(HandSet::Interaction),
+        );
+        #[cfg(feature = "rapier")]
+        app.add_plugins(colliders::rapier::HandRapierPlugin);
+        app.add_systems(
+            Update,
+            (
+                (
+                    apply_hand_visual_settings,
+                    update_hand_tracking_status,
+                    update_hand_mesh,
+                    update_hand_material,
+                )
+                    .chain(),
+                update_skinned_hands.after(update_hand_tracking_status),
+                update_skinned_hand_render_layers,
+                draw_hand_aim_rays,
+            )
+                .in_set(HandSet::Visuals),
+        );
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn apply_hand_visual_settings(
+    settings: Res<HandVisualSettings>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<HandMaterial>>,
+    mut commands: Commands,
+    mut hands: Query<(
+        Entity,
+        Option<Ref<HandVisualSettings>>,
+        Ref<DefaultHandMaterial>,
+        &mut HandMeshTopologyState,
+    )>,
+    mut removed: RemovedComponents<HandVisualSettings>,
+) {
+    if settings.is_changed() {
+        images.insert(&GRADIENT_TEXTURE_HANDLE, create_gradient_texture(&settings));
+    }
+    let removed = removed.read().collect::<Vec<_>>();
+    for (e, hand_settings, default_material, mut state) in &mut hands {
+        let changed = match &hand_settings {
+            Some(hand_settings) => hand_settings.is_changed(),
+            None => settings.is_changed() || removed.contains(&e),
+        };
+        if !changed && !default_material.is_added() {
+            continue;
+        }
+        // Forces the colors to be regenerated with the new tints
+        state.fingers = None;
+
+        let hand_settings = hand_settings.as_deref().unwrap_or(&settings);
+        commands
+            .entity(e)
+            .insert(hand_settings.render_layers.clone());
+        if let Some(material) = &hand_settings.material {
+            commands
+                .entity(e)
+                .remove::<MeshMaterial3d<HandMaterial>>()
+                .insert(MeshMaterial3d(material.clone()));
+            continue;
+        }
+        let gradient = match hand_settings == &*settings {
+            true => GRADIENT_TEXTURE_HANDLE,
+            false => images.add(create_gradient_texture(hand_settings)),
+        };
+        if let Some(material) = materials.get_mut(&default_material.0) {
+            material.gradient_texture = Some(gradient);
+            material.rim_color = hand_settings.rim_color;
+            material.glow_color = hand_settings.glow_color;
+        }
+        commands
+            .entity(e)
+            .remove::<MeshMaterial3d<StandardMaterial>>()
+            .insert(MeshMaterial3d(default_material.0.clone()));
     }
 }
 
+#[expect(clippy::type_complexity)]
 fn update_hand_mesh(
+    settings: Res<HandVisualSettings>,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut hand_mesh: Query<(&Mesh3d, &mut Aabb, &XrHandBoneEntities)>,
-    joint_query: Query<(&GlobalTransform, &XrHandBoneRadius, &XrSpaceLocationFlags)>,
+    mut topologies: ResMut<HandMeshTopologies>,
+    mut hand_mesh: Query<(
+        &Mesh3d,
+        &mut Aabb,
+        &mut HandMeshTopologyState,
+        &mut HandMeshStats,
+        &HandJoints,
+        &HandTrackingStatus,
+        Option<&HandVisualSettings>,
+    )>,
 ) {
-    for (mesh_handle, mut aabb, entities) in hand_mesh.iter_mut() {
-        let Ok(entities) = joint_query.get_many(entities.0) else {
-            warn!("Invalid Hand Joint Entities!");
+    for (mesh_handle, mut aabb, mut state, mut stats, joints, status, hand_settings) in
+        hand_mesh.iter_mut()
+    {
+        let settings = hand_settings.unwrap_or(&settings);
+        let tracked = status.visible_fingers;
+        if tracked.is_empty() || status.alpha <= 0.0 {
+            continue;
+        }
+        let Some(mesh) = meshes.get_mut(&mesh_handle.0) else {
             continue;
         };
-        let data = entities.map(|(transform, radius, _)| {
-            let (_, orientation, position) = transform.to_scale_rotation_translation();
-            HandJoint {
-                position,
-                orientation,
-                radius: radius.0,
-            }
-        });
-        let vert_count = (RING_COUNT * FingerJoint::NUM + 1) * Finger::NUM;
-        let mut positions = Vec::with_capacity(vert_count);
-        let mut normals = Vec::with_capacity(vert_count);
-        let mut colors = Vec::with_capacity(vert_count);
-        let mut uvs = Vec::with_capacity(vert_count);
-        let mut indices = Vec::new();
-
-        let mut i = 0;
-        let mut fingers = Finger::ALL;
-        fingers.reverse();
-        for finger in fingers {
-            let (_, _, flag) = entities[finger.hand_bone(&FingerJoint::Tip) as usize];
-            if (!flag.position_tracked) || (!flag.rotation_tracked) {
-                continue;
-            }
-            let f = SkHandFinger(finger);
-            // Doesn't technically need to be re-generated every frame
-            indices.extend(f.indices(i));
-            colors.extend(f.gen_vertex_colors());
-            uvs.extend(f.gen_uvs(finger));
+        let start = Instant::now();
 
-            // This does need to be re-generated every frame
-            let (poses, norms) = f.gen_vertex_positions_and_normals(&data);
-            positions.extend(poses);
-            normals.extend(norms);
-            i += 1;
+        let topology = topologies.get(tracked, settings.lod);
+        let topology_changed = state.fingers != Some(tracked);
+        if topology_changed {
+            mesh.insert_indices(topology.indices.clone());
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, topology.uvs.clone());
+            state.colors = topology.colors(settings);
+            state.fingers = Some(tracked);
         }
-        if positions.is_empty() {
-            continue;
+        // Fading only changes the alpha, so rescale the cached colors into the old buffer
+        if topology_changed || state.alpha != status.alpha {
+            let mut colors = match mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR) {
+                Some(VertexAttributeValues::Float32x4(values)) => values,
+                _ => Vec::new(),
+            };
+            colors.clear();
+            colors.extend(
+                state
+                    .colors
+                    .iter()
+                    .map(|&[r, g, b, a]| [r, g, b, a * status.alpha]),
+            );
+            mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+            state.alpha = status.alpha;
         }
-        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all());
-        mesh.insert_indices(Indices::U16(indices));
-        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-        let bb = mesh.compute_aabb();
-        meshes.insert(mesh_handle, mesh);
 
-        if let Some(bb) = bb {
-            *aabb = bb
+        // Only positions and normals change between frames, so rewrite them in place
+        let mut positions = take_float32x3_attribute(mesh, Mesh::ATTRIBUTE_POSITION);
+        let mut normals = take_float32x3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL);
+        write_hand_mesh_vertices(
+            &joints.joints,
+            topology,
+            settings,
+            &mut positions,
+            &mut normals,
+        );
+        if let Some(bb) = Aabb::enclosing(positions.iter().copied().map(Vec3::from)) {
+            *aabb = bb;
         }
+        *stats = HandMeshStats {
+            generation_time: start.elapsed(),
+            vertex_count: positions.len(),
+            index_count: mesh.indices().map_or(0, Indices::len),
+        };
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    }
+}
+
+fn take_float32x3_attribute(mesh: &mut Mesh, attribute: MeshVertexAttribute) -> Vec<[f32; 3]> {
+    match mesh.remove_attribute(attribute) {
+        Some(VertexAttributeValues::Float32x3(values)) => values,
+        _ => Vec::new(),
     }
 }
 
-fn create_gradient_texture() -> Image {
+fn create_gradient_texture(settings: &HandVisualSettings) -> Image {
     let width = 16;
     let height = 16;
     let mut gradient = Vec::with_capacity(width * height * 4);
 
     for y in 0..height {
         let t = 1.0 - (y as f32 / (height - 1) as f32);
-        let color = get_gradient_color(t);
+        let color = settings.gradient_color(t);
 
         for _ in 0..width {
             gradient.extend_from_slice(&color);
@@ -465,3 +1164,107 @@ fn create_gradient_texture() -> Image {
         Default::default(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LODS: [HandMeshLod; 5] = [
+        HandMeshLod::Low,
+        HandMeshLod::Medium,
+        HandMeshLod::High,
+        HandMeshLod::Custom(0),
+        HandMeshLod::Custom(7),
+    ];
+
+    fn indices(topology: &HandMeshTopology) -> Vec<usize> {
+        topology.indices.iter().collect()
+    }
+
+    #[test]
+    fn mesh_ring() {
+        for lod in LODS {
+            let ring = HandMeshRing::new(lod);
+            let segments = lod.ring_segments();
+            assert!(segments >= 3);
+            // Both arcs repeat the seam vertices
+            assert_eq!(ring.vertex_count(), segments + 2);
+            assert_eq!(ring.normals.len(), ring.vertex_count());
+            assert!(ring.top_segments >= 1 && ring.top_segments <= segments - 2);
+            for (v, (position, normal)) in ring.positions.iter().zip(&ring.normals).enumerate() {
+                assert!((position.length() - 1.0).abs() < 1e-5);
+                assert!((normal.length() - 1.0).abs() < 1e-5);
+                // The top arc spans 162° to 18°
+                let seam = 18f32.to_radians().sin();
+                match ring.is_top(v) {
+                    true => assert!(position.y >= seam - 1e-5),
+                    false => assert!(position.y <= seam + 1e-5),
+                }
+            }
+            let (first, last) = (0, ring.vertex_count() - 1);
+            for (a, b) in [(ring.top_segments, ring.top_segments + 1), (first, last)] {
+                assert!(ring.positions[a].abs_diff_eq(ring.positions[b], 1e-5));
+            }
+        }
+    }
+
+    #[test]
+    fn mesh_indices() {
+        for lod in LODS {
+            let topology = HandMeshTopology::new(TrackedFingers::all(), lod);
+            let ring = HandMeshRing::new(lod);
+            assert_eq!(
+                topology.vertex_count(),
+                ring.finger_vertex_count() * Finger::NUM
+            );
+            assert!(matches!(topology.indices, Indices::U16(_)));
+            let indices = indices(&topology);
+            assert!(indices.len().is_multiple_of(3));
+            for triangle in indices.chunks_exact(3) {
+                assert!(triangle.iter().all(|i| *i < topology.vertex_count()));
+                assert!(
+                    triangle[0] != triangle[1]
+                        && triangle[1] != triangle[2]
+                        && triangle[0] != triangle[2]
+                );
+            }
+            for v in 0..topology.vertex_count() {
+                assert!(indices.contains(&v), "{lod:?} vertex {v} is unused");
+            }
+        }
+    }
+
+    #[test]
+    fn medium_mesh_matches_stereokit() {
+        let topology = HandMeshTopology::new(TrackedFingers::INDEX, HandMeshLod::Medium);
+        assert_eq!(topology.vertex_count(), 43);
+        assert_eq!(topology.indices.len(), 174);
+    }
+
+    #[test]
+    fn partial_mesh_indices() {
+        let topology = HandMeshTopology::new(
+            TrackedFingers::THUMB | TrackedFingers::RING,
+            HandMeshLod::Medium,
+        );
+        let full = HandMeshTopology::new(TrackedFingers::all(), HandMeshLod::Medium);
+        assert_eq!(topology.vertex_count() * 5, full.vertex_count() * 2);
+        assert_eq!(topology.indices.len() * 5, full.indices.len() * 2);
+        assert!(
+            indices(&topology)
+                .iter()
+                .all(|i| *i < topology.vertex_count())
+        );
+    }
+
+    #[test]
+    fn mesh_indices_fall_back_to_u32() {
+        let topology = HandMeshTopology::new(TrackedFingers::all(), HandMeshLod::Custom(2500));
+        assert!(topology.vertex_count() > u16::MAX as usize + 1);
+        let Indices::U32(values) = &topology.indices else {
+            panic!("expected u32 indices");
+        };
+        let max = *values.iter().max().unwrap() as usize;
+        assert_eq!(max, topology.vertex_count() - 1);
+    }
+}
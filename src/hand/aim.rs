@@ -0,0 +1,155 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_mod_xr::camera::XrCamera;
+use bevy_mod_xr::hands::HandBone;
+
+use super::{HandJoints, Handed};
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandAimSettings {
+    /// Distance between the estimated shoulders.
+    pub shoulder_width: f32,
+    /// How far the shoulders sit below the head.
+    pub shoulder_drop: f32,
+    /// How quickly the ray follows the hand, higher values smooth less.
+    pub smoothing_speed: f32,
+    /// Draw the aim rays with gizmos.
+    pub show_ray: bool,
+    pub ray_length: f32,
+    pub ray_color: Color,
+}
+
+impl Default for HandAimSettings {
+    fn default() -> Self {
+        Self {
+            shoulder_width: 0.34,
+            shoulder_drop: 0.25,
+            smoothing_speed: 20.0,
+            show_ray: false,
+            ray_length: 2.0,
+            ray_color: Color::WHITE,
+        }
+    }
+}
+
+/// Far interaction ray cast from the estimated shoulder through the hand.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HandAimRay {
+    pub origin: Vec3,
+    pub direction: Dir3,
+    /// Whether the joints needed for the ray are currently tracked.
+    pub valid: bool,
+}
+
+impl Default for HandAimRay {
+    fn default() -> Self {
+        Self {
+            origin: Vec3::ZERO,
+            direction: Dir3::NEG_Z,
+            valid: false,
+        }
+    }
+}
+
+impl HandAimRay {
+    pub fn ray(&self) -> Ray3d {
+        Ray3d::new(self.origin, self.direction)
+    }
+}
+
+/// Estimates the shoulder of a hand from the head pose, ignoring head pitch and roll.
+pub fn estimate_shoulder(head: &Transform, handed: Handed, settings: &HandAimSettings) -> Vec3 {
+    let forward = head.forward().with_y(0.0).normalize_or(Vec3::NEG_Z);
+    let right = forward.cross(Vec3::Y);
+    head.translation + right * settings.shoulder_width * 0.5 * handed.side()
+        - Vec3::Y * settings.shoulder_drop
+}
+
+/// Estimates the head pose from the XR view cameras, or from the only 3D camera outside of XR.
+#[derive(SystemParam)]
+pub struct HeadPose<'w, 's> {
+    xr_cameras: Query<'w, 's, &'static GlobalTransform, With<XrCamera>>,
+    cameras: Query<'w, 's, &'static GlobalTransform, With<Camera3d>>,
+}
+
+impl HeadPose<'_, '_> {
+    pub fn get(&self) -> Option<Transform> {
+        if self.xr_cameras.is_empty() {
+            // Spectator and mirror cameras can't be told apart from a flat camera, so only trust a
+            // lone one
+            return self
+                .cameras
+                .single()
+                .ok()
+                .map(GlobalTransform::compute_transform);
+        }
+        // Stereo rigs have a camera per eye, so average them to get the head
+        let mut head = Transform::IDENTITY;
+        let mut camera_count = 0;
+        for camera in &self.xr_cameras {
+            let camera = camera.compute_transform();
+            head.translation += camera.translation;
+            head.rotation = camera.rotation;
+            camera_count += 1;
+        }
+        head.translation /= camera_count as f32;
+        Some(head)
+    }
+}
+
+pub(crate) fn update_hand_aim_rays(
+    settings: Res<HandAimSettings>,
+    time: Res<Time>,
+    head: HeadPose,
+    mut hands: Query<(&HandJoints, &mut HandAimRay, &Handed)>,
+) {
+    let Some(head) = head.get() else {
+        return;
+    };
+
+    let blend = 1.0 - (-settings.smoothing_speed * time.delta_secs()).exp();
+    for (joints, mut aim, handed) in &mut hands {
+        let bones = [HandBone::IndexProximal, HandBone::ThumbProximal];
+        if !bones.iter().all(|bone| joints.is_tracked(*bone)) {
+            aim.valid = false;
+            continue;
+        }
+        let origin = joints
+            .get(HandBone::IndexProximal)
+            .position
+            .lerp(joints.get(HandBone::ThumbProximal).position, 0.5);
+        let shoulder = estimate_shoulder(&head, *handed, &settings);
+        let Ok(direction) = Dir3::new(origin - shoulder) else {
+            continue;
+        };
+
+        // Snap to the new ray when tracking resumes instead of sweeping across the scene
+        if aim.valid {
+            aim.origin = aim.origin.lerp(origin, blend);
+            aim.direction = aim.direction.slerp(direction, blend);
+        } else {
+            aim.origin = origin;
+            aim.direction = direction;
+        }
+        aim.valid = true;
+    }
+}
+
+pub(crate) fn draw_hand_aim_rays(
+    settings: Res<HandAimSettings>,
+    hands: Query<&HandAimRay>,
+    mut gizmos: Gizmos,
+) {
+    if !settings.show_ray {
+        return;
+    }
+    for aim in &hands {
+        if aim.valid {
+            gizmos.ray(
+                aim.origin,
+                aim.direction * settings.ray_length,
+                settings.ray_color,
+            );
+        }
+    }
+}
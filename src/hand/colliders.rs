@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HandBone;
+
+use super::{Finger, FingerJoint, HandJoints};
+
+#[cfg(feature = "rapier")]
+pub mod rapier;
+
+/// A capsule between two hand joints, in world space.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HandCapsule {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub radius: f32,
+    /// Whether both joints are tracked, colliders should be disabled otherwise.
+    pub active: bool,
+}
+
+impl HandCapsule {
+    pub fn center(&self) -> Vec3 {
+        self.start.lerp(self.end, 0.5)
+    }
+
+    /// Distance between the centers of the end caps.
+    pub fn length(&self) -> f32 {
+        self.start.distance(self.end)
+    }
+
+    /// Rotation taking +Y onto the capsule's axis, as most physics engines orient capsules.
+    pub fn rotation(&self) -> Quat {
+        let axis = (self.end - self.start).normalize_or(Vec3::Y);
+        Quat::from_rotation_arc(Vec3::Y, axis)
+    }
+}
+
+/// An oriented box covering the palm, in world space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandPalmBox {
+    pub center: Vec3,
+    pub rotation: Quat,
+    pub half_size: Vec3,
+    pub active: bool,
+}
+
+impl Default for HandPalmBox {
+    fn default() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            half_size: Vec3::ZERO,
+            active: false,
+        }
+    }
+}
+
+/// Physics engine agnostic collision shapes of a hand, updated from its [`HandJoints`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandColliders {
+    /// A capsule per finger bone, indexed by [`Finger`] and then by the joint the bone ends at,
+    /// from [`FingerJoint::Proximal`] to [`FingerJoint::Tip`].
+    pub bones: [[HandCapsule; FingerJoint::NUM - 1]; Finger::NUM],
+    pub palm: HandPalmBox,
+}
+
+impl HandColliders {
+    pub fn bone(&self, finger: Finger, end: FingerJoint) -> Option<&HandCapsule> {
+        let index = (end as usize).checked_sub(1)?;
+        Some(&self.bones[finger as usize][index])
+    }
+
+    pub fn capsules(&self) -> impl Iterator<Item = &HandCapsule> {
+        self.bones.iter().flatten()
+    }
+}
+
+pub(crate) fn update_hand_colliders(mut hands: Query<(&HandJoints, &mut HandColliders)>) {
+    for (joints, mut colliders) in &mut hands {
+        for finger in Finger::ALL {
+            for (capsule, end) in colliders.bones[finger as usize]
+                .iter_mut()
+                .zip(&FingerJoint::ALL[1..])
+            {
+                let start = finger.hand_bone(&end.previous_in_chain());
+                let end = finger.hand_bone(end);
+                let (a, b) = (joints.get(start), joints.get(end));
+                *capsule = HandCapsule {
+                    start: a.position,
+                    end: b.position,
+                    radius: (a.radius + b.radius) * 0.5,
+                    active: joints.is_tracked(start) && joints.is_tracked(end),
+                };
+            }
+        }
+
+        let palm = joints.get(HandBone::Palm);
+        let index = joints.get(HandBone::IndexProximal);
+        let little = joints.get(HandBone::LittleProximal);
+        let middle = joints.get(HandBone::MiddleMetacarpal);
+        let width = index.position.distance(little.position) + index.radius + little.radius;
+        let length = joints
+            .get(HandBone::Wrist)
+            .position
+            .distance(joints.get(HandBone::MiddleProximal).position);
+        colliders.palm = HandPalmBox {
+            center: palm.position,
+            rotation: palm.orientation,
+            half_size: Vec3::new(width, middle.radius * 2.0, length) * 0.5,
+            active: [
+                HandBone::Palm,
+                HandBone::Wrist,
+                HandBone::IndexProximal,
+                HandBone::MiddleProximal,
+                HandBone::LittleProximal,
+            ]
+            .into_iter()
+            .all(|bone| joints.is_tracked(bone)),
+        };
+    }
+}
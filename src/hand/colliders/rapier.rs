@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, ColliderDisabled, RigidBody};
+
+use super::HandColliders;
+use crate::hand::{Finger, FingerJoint, HandSet};
+
+/// Gives every hand with [`HandColliders`] kinematic rapier colliders that follow its bones,
+/// letting it push dynamic bodies around.
+///
+/// Added by the hand plugin when the `rapier` feature is enabled, `RapierPhysicsPlugin` still
+/// has to be added by the app.
+pub struct HandRapierPlugin;
+
+impl Plugin for HandRapierPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn_hand_rapier_colliders);
+        app.add_systems(
+            Update,
+            update_hand_rapier_colliders
+                .after(super::update_hand_colliders)
+                .in_set(HandSet::Interaction),
+        );
+    }
+}
+
+/// Which part of its hand a collider entity follows.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HandColliderPart {
+    /// The bone of the finger ending at the joint.
+    Bone(Finger, FingerJoint),
+    Palm,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+#[relationship(relationship_target = HandRapierColliders)]
+pub struct HandRapierColliderOf(pub Entity);
+
+/// The collider entities following a hand, despawned along with it.
+#[derive(Component, Debug)]
+#[relationship_target(relationship = HandRapierColliderOf, linked_spawn)]
+pub struct HandRapierColliders(Vec<Entity>);
+
+impl HandRapierColliders {
+    pub fn colliders(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+/// Shape the collider was last built with, to avoid rebuilding it every frame.
+#[derive(Component, Default)]
+struct ColliderSize(Vec3);
+
+/// How much a bone can change size before its collider is rebuilt.
+const RESIZE_TOLERANCE: f32 = 0.001;
+
+fn spawn_hand_rapier_colliders(trigger: Trigger<OnAdd, HandColliders>, mut commands: Commands) {
+    let hand = trigger.target();
+    let bones = Finger::ALL.into_iter().flat_map(|finger| {
+        FingerJoint::ALL[1..]
+            .iter()
+            .map(move |joint| HandColliderPart::Bone(finger, *joint))
+    });
+    for part in bones.chain([HandColliderPart::Palm]) {
+        commands.spawn((
+            Name::new(format!("Hand Collider {part:?}")),
+            HandRapierColliderOf(hand),
+            part,
+            RigidBody::KinematicPositionBased,
+            // Sized on the first update
+            Collider::ball(RESIZE_TOLERANCE),
+            ColliderSize::default(),
+            ColliderDisabled,
+            Transform::default(),
+        ));
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn update_hand_rapier_colliders(
+    hands: Query<&HandColliders>,
+    mut colliders: Query<(
+        Entity,
+        &HandRapierColliderOf,
+        &HandColliderPart,
+        &mut Transform,
+        &mut Collider,
+        &mut ColliderSize,
+        Has<ColliderDisabled>,
+    )>,
+    mut commands: Commands,
+) {
+    for (e, hand, part, mut transform, mut collider, mut size, disabled) in &mut colliders {
+        let Ok(hand) = hands.get(hand.0) else {
+            continue;
+        };
+        let (translation, rotation, new_size, active) = match part {
+            HandColliderPart::Bone(finger, joint) => {
+                let Some(capsule) = hand.bone(*finger, *joint) else {
+                    continue;
+                };
+                (
+                    capsule.center(),
+                    capsule.rotation(),
+                    Vec3::new(capsule.radius, capsule.length() * 0.5, 0.0),
+                    capsule.active,
+                )
+            }
+            HandColliderPart::Palm => (
+                hand.palm.center,
+                hand.palm.rotation,
+                hand.palm.half_size,
+                hand.palm.active,
+            ),
+        };
+
+        match (active, disabled) {
+            (true, true) => {
+                commands.entity(e).remove::<ColliderDisabled>();
+            }
+            (false, false) => {
+                commands.entity(e).insert(ColliderDisabled);
+            }
+            _ => {}
+        }
+        if !active {
+            continue;
+        }
+
+        transform.translation = translation;
+        transform.rotation = rotation;
+        if size.0.abs_diff_eq(new_size, RESIZE_TOLERANCE) {
+            continue;
+        }
+        size.0 = new_size;
+        *collider = match part {
+            HandColliderPart::Bone(..) => Collider::capsule_y(new_size.y, new_size.x),
+            HandColliderPart::Palm => Collider::cuboid(new_size.x, new_size.y, new_size.z),
+        };
+    }
+}
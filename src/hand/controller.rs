@@ -0,0 +1,170 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{XrHandBoneEntities, XrHandBoneRadius};
+use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+
+use super::simulator::{HandPose, insert_hand_marker, spawn_hand_bones, write_hand_bones};
+use super::tracking::HandTrackingStatus;
+use super::{Finger, HandSet, Handed};
+
+/// Spawns a pair of hands animated from controller input, for when hand tracking is unavailable.
+///
+/// Each controller hand is only shown while no other hand on its side is tracked, so it takes over
+/// whenever hand tracking is lost, even though runtimes keep their hand trackers around. The app
+/// feeds each hand's [`ControllerHandInput`] from its XR actions, before [`HandSet::Joints`].
+pub struct ControllerHandPlugin;
+
+impl Plugin for ControllerHandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControllerHandSettings>();
+        app.add_systems(Startup, spawn_controller_hands);
+        app.add_systems(
+            Update,
+            (activate_controller_hands, update_controller_hands)
+                .chain()
+                .before(HandSet::Joints),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ControllerHandSettings {
+    /// Pose of the hand with nothing pressed.
+    pub open: HandPose,
+    /// Pose of the hand with everything pressed, each finger blends towards it separately.
+    pub closed: HandPose,
+    /// Wrist of the right hand relative to the controller's grip pose, mirrored for the left hand.
+    pub wrist_offset: Transform,
+}
+
+impl Default for ControllerHandSettings {
+    fn default() -> Self {
+        Self {
+            open: HandPose {
+                curls: [0.2, 0.3, 0.55, 0.6, 0.65],
+                thumb_reach: 0.2,
+            },
+            closed: HandPose::FIST,
+            // Palm wrapped around the handle, thumb on top
+            wrist_offset: Transform::from_xyz(0.03, 0.0, 0.07)
+                .with_rotation(Quat::from_rotation_z(-FRAC_PI_2)),
+        }
+    }
+}
+
+/// Controller state a [`ControllerHand`] is animated from.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ControllerHandInput {
+    /// World space grip pose of the controller.
+    pub grip_pose: Transform,
+    /// Whether the grip pose is valid, the hand fades out like a lost tracked hand otherwise.
+    pub tracked: bool,
+    /// Curls the index finger, from 0 to 1.
+    pub trigger: f32,
+    /// Curls the middle, ring and little fingers, from 0 to 1.
+    pub grip: f32,
+    /// Lowers the thumb, from 0 to 1. Controllers with capacitive sensing report whether
+    /// the thumb rests on the face buttons or thumbstick.
+    pub thumb_rest: f32,
+}
+
+impl ControllerHandInput {
+    /// Blends `open` towards `closed` per finger, following the pressed inputs.
+    pub fn pose(&self, open: &HandPose, closed: &HandPose) -> HandPose {
+        let mut pose = *open;
+        for finger in Finger::ALL {
+            let amount = match finger {
+                Finger::Thumb => self.thumb_rest,
+                Finger::Index => self.trigger,
+                Finger::Middle | Finger::Ring | Finger::Little => self.grip,
+            };
+            let i = finger as usize;
+            pose.curls[i] = open.curls[i].lerp(closed.curls[i], amount.clamp(0.0, 1.0));
+        }
+        pose.thumb_reach = open
+            .thumb_reach
+            .lerp(closed.thumb_reach, self.thumb_rest.clamp(0.0, 1.0));
+        pose
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+#[require(ControllerHandInput)]
+pub struct ControllerHand {
+    pub handed: Handed,
+    /// Whether the hand is shown, false while another hand on the same side is tracked.
+    pub active: bool,
+}
+
+fn spawn_controller_hands(mut commands: Commands) {
+    for handed in Handed::BOTH {
+        let bones = spawn_hand_bones(&mut commands, handed);
+        let mut cmds = commands.spawn((
+            bones,
+            ControllerHand {
+                handed,
+                active: true,
+            },
+        ));
+        insert_hand_marker(&mut cmds, handed);
+    }
+}
+
+/// Hides each controller hand while another hand on its side is tracked, and shows it again once
+/// that hand is lost.
+fn activate_controller_hands(
+    mut controller_hands: Query<&mut ControllerHand>,
+    other_hands: Query<(&Handed, &HandTrackingStatus), Without<ControllerHand>>,
+) {
+    for mut hand in &mut controller_hands {
+        let tracked = other_hands.iter().any(|(handed, status)| {
+            *handed == hand.handed && !status.is_lost() && !status.is_never_tracked()
+        });
+        hand.set_if_neq(ControllerHand {
+            active: !tracked,
+            ..*hand
+        });
+    }
+}
+
+fn update_controller_hands(
+    settings: Res<ControllerHandSettings>,
+    hands: Query<(&ControllerHand, &ControllerHandInput, &XrHandBoneEntities)>,
+    mut bones: Query<(&mut Transform, &mut GlobalTransform, &mut XrHandBoneRadius)>,
+    mut flags: Query<&mut XrSpaceLocationFlags>,
+) {
+    for (hand, input, entities) in &hands {
+        // Untracked bones fade the hand out like any other lost hand
+        let tracked = hand.active && input.tracked;
+        for entity in entities.0 {
+            if let Ok(mut flags) = flags.get_mut(entity) {
+                flags.position_tracked = tracked;
+                flags.rotation_tracked = tracked;
+            }
+        }
+        if !tracked {
+            continue;
+        }
+
+        let mut offset = settings.wrist_offset;
+        if hand.handed == Handed::Left {
+            offset.translation.x = -offset.translation.x;
+            offset.rotation = Quat::from_xyzw(
+                offset.rotation.x,
+                -offset.rotation.y,
+                -offset.rotation.z,
+                offset.rotation.w,
+            );
+        }
+        let wrist = input.grip_pose * offset;
+        let joints = input
+            .pose(&settings.open, &settings.closed)
+            .joints(&wrist, hand.handed);
+        let Ok(bones) = bones.get_many_mut(entities.0) else {
+            warn!("Invalid Hand Joint Entities!");
+            continue;
+        };
+        write_hand_bones(bones, joints);
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{HandBone, XrHandBoneEntities};
+use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+
+use super::{
+    Finger, FingerJoint, HandJoints, HandMeshTopologies, HandSet, HandVisualSettings,
+    TrackedFingers, write_hand_mesh_vertices,
+};
+
+/// Draws the joints, bones and generated mesh rings of every hand with gizmos, to diagnose
+/// tracking and mesh issues.
+///
+/// Toggle it with [`HandDebugSettings::toggle_key`] or [`HandDebugSettings::enabled`].
+pub struct HandDebugPlugin;
+
+impl Plugin for HandDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HandDebugSettings>();
+        // Draw through the hand mesh
+        app.insert_gizmo_config(
+            HandDebugGizmos,
+            GizmoConfig {
+                depth_bias: -1.0,
+                ..default()
+            },
+        );
+        app.add_systems(
+            Update,
+            (toggle_hand_debug, draw_hand_debug)
+                .chain()
+                .in_set(HandSet::Visuals),
+        );
+    }
+}
+
+/// Gizmo group of the [`HandDebugPlugin`], configure it through the [`GizmoConfigStore`].
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct HandDebugGizmos;
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandDebugSettings {
+    pub enabled: bool,
+    /// Flips `enabled` when pressed.
+    pub toggle_key: Option<KeyCode>,
+    /// Draw the orientation of each joint.
+    pub show_axes: bool,
+    pub axis_length: f32,
+    /// Draw a sphere with the radius of each joint, colored by its tracking flags.
+    pub show_radii: bool,
+    /// Draw lines from the wrist through the joints of each finger.
+    pub show_bones: bool,
+    /// Draw the vertex rings the hand mesh is generated from.
+    pub show_rings: bool,
+    /// Length of the mesh normals drawn at each ring vertex, 0 to hide them.
+    pub normal_length: f32,
+    pub bone_color: Color,
+    pub ring_color: Color,
+    pub normal_color: Color,
+    /// Joints with both their position and rotation tracked.
+    pub tracked_color: Color,
+    /// Joints with only their position or rotation tracked.
+    pub partially_tracked_color: Color,
+    pub untracked_color: Color,
+}
+
+impl Default for HandDebugSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: Some(KeyCode::F3),
+            show_axes: true,
+            axis_length: 0.015,
+            show_radii: true,
+            show_bones: true,
+            show_rings: false,
+            normal_length: 0.005,
+            bone_color: Color::WHITE,
+            ring_color: Color::srgb(0.3, 0.7, 1.0),
+            normal_color: Color::srgb(1.0, 0.3, 1.0),
+            tracked_color: Color::srgb(0.2, 1.0, 0.2),
+            partially_tracked_color: Color::srgb(1.0, 0.8, 0.0),
+            untracked_color: Color::srgb(1.0, 0.2, 0.2),
+        }
+    }
+}
+
+impl HandDebugSettings {
+    pub fn tracking_color(&self, flags: &XrSpaceLocationFlags) -> Color {
+        match (flags.position_tracked, flags.rotation_tracked) {
+            (true, true) => self.tracked_color,
+            (false, false) => self.untracked_color,
+            _ => self.partially_tracked_color,
+        }
+    }
+}
+
+fn toggle_hand_debug(
+    mut settings: ResMut<HandDebugSettings>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let Some(key) = settings.toggle_key else {
+        return;
+    };
+    if keys.is_some_and(|keys| keys.just_pressed(key)) {
+        settings.enabled = !settings.enabled;
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn draw_hand_debug(
+    settings: Res<HandDebugSettings>,
+    visual_settings: Res<HandVisualSettings>,
+    hands: Query<(
+        &HandJoints,
+        Option<&XrHandBoneEntities>,
+        Option<&HandVisualSettings>,
+    )>,
+    flags: Query<&XrSpaceLocationFlags>,
+    mut topologies: ResMut<HandMeshTopologies>,
+    mut gizmos: Gizmos<HandDebugGizmos>,
+    mut buffers: Local<(Vec<[f32; 3]>, Vec<[f32; 3]>)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let (positions, normals) = &mut *buffers;
+    for (joints, entities, hand_settings) in &hands {
+        for (i, joint) in joints.joints.iter().enumerate() {
+            if settings.show_axes {
+                gizmos.axes(
+                    Transform::from_translation(joint.position).with_rotation(joint.orientation),
+                    settings.axis_length,
+                );
+            }
+            if settings.show_radii && joint.radius > 0.0 {
+                gizmos.sphere(
+                    Isometry3d::new(joint.position, joint.orientation),
+                    joint.radius,
+                    match entities.and_then(|entities| flags.get(entities.0[i]).ok()) {
+                        Some(flags) => settings.tracking_color(flags),
+                        // Sources without separate position and rotation flags
+                        None if joints.tracked[i] => settings.tracked_color,
+                        None => settings.untracked_color,
+                    },
+                );
+            }
+        }
+
+        if settings.show_bones {
+            for finger in Finger::ALL {
+                let chain = FingerJoint::ALL
+                    .iter()
+                    .map(|joint| finger.hand_bone(joint))
+                    .filter(|bone| !matches!(bone, HandBone::Wrist));
+                gizmos.linestrip(
+                    [HandBone::Wrist]
+                        .into_iter()
+                        .chain(chain)
+                        .map(|bone| joints.get(bone).position),
+                    settings.bone_color,
+                );
+            }
+        }
+
+        if settings.show_rings {
+            let hand_settings = hand_settings.unwrap_or(&visual_settings);
+            let topology = topologies.get(TrackedFingers::all(), hand_settings.lod);
+            write_hand_mesh_vertices(
+                &joints.joints,
+                topology,
+                hand_settings,
+                positions,
+                normals,
+            );
+            let ring = &topology.ring;
+            for finger in positions.chunks_exact(ring.finger_vertex_count()) {
+                // The last vertex of a finger is the tip, the rest are rings
+                for ring in finger[..finger.len() - 1].chunks_exact(ring.vertex_count()) {
+                    gizmos.linestrip(ring.iter().copied().map(Vec3::from), settings.ring_color);
+                }
+            }
+            if settings.normal_length > 0.0 {
+                for (position, normal) in positions.iter().zip(normals.iter()) {
+                    let position = Vec3::from(*position);
+                    gizmos.line(
+                        position,
+                        position + Vec3::from(*normal).normalize_or_zero() * settings.normal_length,
+                        settings.normal_color,
+                    );
+                }
+            }
+        }
+    }
+}
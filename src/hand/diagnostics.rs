@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{HAND_JOINT_COUNT, HandBone};
+
+use super::filter::filter_hand_joints;
+use super::source::UpdateHandJointsSet;
+use super::tracking::HandTrackingStatus;
+use super::{HandJoint, HandJoints, HandSet, Handed};
+
+/// Publishes hand tracking quality and hand mesh statistics of each hand as diagnostics.
+///
+/// Log them with the `LogDiagnosticsPlugin`, or read them from the
+/// [`DiagnosticsStore`](bevy::diagnostic::DiagnosticsStore) through
+/// [`HandDiagnosticsPlugin::paths`].
+///
+/// Hands are told apart by their [`Handed`], so several hands on the same side, like a tracked
+/// and a simulated one, mix their measurements together. A warning is logged when that happens.
+pub struct HandDiagnosticsPlugin;
+
+impl Plugin for HandDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for handed in Handed::BOTH {
+            let paths = Self::paths(handed);
+            app.register_diagnostic(Diagnostic::new(paths.tracked_joints).with_suffix("%"))
+                .register_diagnostic(
+                    Diagnostic::new(paths.tracking_losses)
+                        .with_suffix("/min")
+                        .with_smoothing_factor(0.0),
+                )
+                .register_diagnostic(Diagnostic::new(paths.joint_update_rate).with_suffix("Hz"))
+                .register_diagnostic(Diagnostic::new(paths.mesh_generation_time).with_suffix("ms"))
+                .register_diagnostic(
+                    Diagnostic::new(paths.mesh_vertices).with_smoothing_factor(0.0),
+                )
+                .register_diagnostic(
+                    Diagnostic::new(paths.mesh_indices).with_smoothing_factor(0.0),
+                );
+        }
+        app.register_required_components::<HandJoints, HandDiagnosticsState>();
+        app.add_systems(
+            Update,
+            (
+                measure_hand_joint_updates
+                    .after(UpdateHandJointsSet)
+                    .before(filter_hand_joints)
+                    .in_set(HandSet::Joints),
+                hand_diagnostics_system.after(HandSet::Visuals),
+            ),
+        );
+    }
+}
+
+/// Paths of the diagnostics of one hand.
+#[derive(Clone, Debug)]
+pub struct HandDiagnosticPaths {
+    /// Percentage of joints with their position and rotation tracked.
+    pub tracked_joints: DiagnosticPath,
+    /// Times the whole hand was lost in the last minute.
+    pub tracking_losses: DiagnosticPath,
+    /// How often the runtime reports a new pose for the hand, which can be lower than the
+    /// frame rate.
+    pub joint_update_rate: DiagnosticPath,
+    /// Time spent updating the hand mesh in `update_hand_mesh`.
+    pub mesh_generation_time: DiagnosticPath,
+    pub mesh_vertices: DiagnosticPath,
+    pub mesh_indices: DiagnosticPath,
+}
+
+macro_rules! hand_diagnostic_paths {
+    ($side:literal) => {
+        HandDiagnosticPaths {
+            tracked_joints: DiagnosticPath::const_new(concat!("hand/", $side, "/tracked_joints")),
+            tracking_losses: DiagnosticPath::const_new(concat!("hand/", $side, "/tracking_losses")),
+            joint_update_rate: DiagnosticPath::const_new(concat!(
+                "hand/",
+                $side,
+                "/joint_update_rate"
+            )),
+            mesh_generation_time: DiagnosticPath::const_new(concat!(
+                "hand/",
+                $side,
+                "/mesh_generation_time"
+            )),
+            mesh_vertices: DiagnosticPath::const_new(concat!("hand/", $side, "/mesh_vertices")),
+            mesh_indices: DiagnosticPath::const_new(concat!("hand/", $side, "/mesh_indices")),
+        }
+    };
+}
+
+impl HandDiagnosticsPlugin {
+    pub const LEFT: HandDiagnosticPaths = hand_diagnostic_paths!("left");
+    pub const RIGHT: HandDiagnosticPaths = hand_diagnostic_paths!("right");
+
+    pub const fn paths(handed: Handed) -> HandDiagnosticPaths {
+        match handed {
+            Handed::Left => Self::LEFT,
+            Handed::Right => Self::RIGHT,
+        }
+    }
+}
+
+/// The last update of a hand's generated mesh.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandMeshStats {
+    pub generation_time: Duration,
+    pub vertex_count: usize,
+    pub index_count: usize,
+}
+
+const LOSS_WINDOW: f64 = 60.0;
+
+#[derive(Component, Default)]
+struct HandDiagnosticsState {
+    /// Hands that were never tracked don't count as lost, so they don't start with a loss.
+    was_lost: bool,
+    /// Times the hand was lost within the last [`LOSS_WINDOW`].
+    losses: VecDeque<f64>,
+    last_wrist: Option<HandJoint>,
+    last_update: Option<f64>,
+}
+
+/// Runs before the joints are filtered, as filtering changes them every frame.
+fn measure_hand_joint_updates(
+    mut diagnostics: Diagnostics,
+    time: Res<Time<Real>>,
+    mut hands: Query<(&Handed, &HandJoints, &mut HandDiagnosticsState)>,
+) {
+    let now = time.elapsed_secs_f64();
+    for (handed, joints, mut state) in &mut hands {
+        let paths = HandDiagnosticsPlugin::paths(*handed);
+        // Sources can report the same pose for several frames, so only count new ones
+        let wrist = joints
+            .is_tracked(HandBone::Wrist)
+            .then(|| *joints.get(HandBone::Wrist));
+        if wrist.is_some() && wrist != state.last_wrist {
+            if let Some(last_update) = state.last_update.filter(|t| now > *t) {
+                diagnostics.add_measurement(&paths.joint_update_rate, || 1.0 / (now - last_update));
+            }
+            state.last_update = Some(now);
+        } else if wrist.is_none() {
+            state.last_update = None;
+        }
+        state.last_wrist = wrist;
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn hand_diagnostics_system(
+    mut diagnostics: Diagnostics,
+    time: Res<Time<Real>>,
+    mut hands: Query<(
+        &Handed,
+        &HandJoints,
+        &HandTrackingStatus,
+        Option<Ref<HandMeshStats>>,
+        &mut HandDiagnosticsState,
+    )>,
+    mut warned: Local<bool>,
+) {
+    if !*warned {
+        for side in Handed::BOTH {
+            if hands.iter().filter(|(handed, ..)| **handed == side).count() > 1 {
+                warn!("Several {side:?} hands exist, their diagnostics are mixed together");
+                *warned = true;
+            }
+        }
+    }
+
+    let now = time.elapsed_secs_f64();
+    for (handed, joints, status, mesh_stats, mut state) in &mut hands {
+        let paths = HandDiagnosticsPlugin::paths(*handed);
+
+        let tracked = joints.tracked.iter().filter(|tracked| **tracked).count();
+        diagnostics.add_measurement(&paths.tracked_joints, || {
+            tracked as f64 / HAND_JOINT_COUNT as f64 * 100.0
+        });
+
+        let lost = status.is_lost();
+        if lost && !state.was_lost {
+            state.losses.push_back(now);
+        }
+        state.was_lost = lost;
+        while state.losses.front().is_some_and(|t| now - t > LOSS_WINDOW) {
+            state.losses.pop_front();
+        }
+        diagnostics.add_measurement(&paths.tracking_losses, || state.losses.len() as f64);
+
+        if let Some(stats) = mesh_stats.filter(|stats| stats.is_changed() && !stats.is_added()) {
+            diagnostics.add_measurement(&paths.mesh_generation_time, || {
+                stats.generation_time.as_secs_f64() * 1000.0
+            });
+            diagnostics.add_measurement(&paths.mesh_vertices, || stats.vertex_count as f64);
+            diagnostics.add_measurement(&paths.mesh_indices, || stats.index_count as f64);
+        }
+    }
+}
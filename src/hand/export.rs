@@ -0,0 +1,439 @@
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, MeshVertexAttribute, VertexAttributeValues};
+use bevy::render::render_resource::PrimitiveTopology;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// File formats a mesh can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MeshFormat {
+    /// Wavefront OBJ, with vertex colors appended to the positions.
+    Obj,
+    /// A single `.gltf` file with the buffer embedded as a data URI.
+    Gltf,
+}
+
+impl MeshFormat {
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "obj" => Some(MeshFormat::Obj),
+            "gltf" => Some(MeshFormat::Gltf),
+            _ => None,
+        }
+    }
+}
+
+/// Writes a triangle list mesh to disk, picking the format from the file extension.
+pub fn save_mesh(mesh: &Mesh, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    let Some(format) = MeshFormat::from_path(path) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported mesh format {}", path.display()),
+        ));
+    };
+    let mut writer = BufWriter::new(File::create(path)?);
+    match format {
+        MeshFormat::Obj => write_obj(mesh, &mut writer)?,
+        MeshFormat::Gltf => write_gltf(mesh, &mut writer)?,
+    }
+    writer.flush()
+}
+
+/// Exports the generated mesh of a hand, logging an error on failure.
+///
+/// Queue it with `commands.queue(ExportHandMesh { hand, path })`, the path's extension picks
+/// the [`MeshFormat`].
+#[derive(Clone, Debug)]
+pub struct ExportHandMesh {
+    pub hand: Entity,
+    pub path: PathBuf,
+}
+
+impl Command for ExportHandMesh {
+    fn apply(self, world: &mut World) {
+        let Some(mesh) = world.get::<Mesh3d>(self.hand) else {
+            error!("Hand {} has no generated mesh to export", self.hand);
+            return;
+        };
+        let Some(mesh) = world.resource::<Assets<Mesh>>().get(mesh) else {
+            error!("Hand {} mesh isn't loaded", self.hand);
+            return;
+        };
+        match save_mesh(mesh, &self.path) {
+            Ok(()) => info!("Exported hand mesh to {}", self.path.display()),
+            Err(err) => error!(
+                "Failed to export hand mesh to {}: {err}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// The attributes the exporters write.
+struct MeshData<'a> {
+    positions: &'a [[f32; 3]],
+    normals: Option<&'a [[f32; 3]]>,
+    colors: Option<&'a [[f32; 4]]>,
+    uvs: Option<&'a [[f32; 2]]>,
+    indices: Option<&'a Indices>,
+}
+
+impl<'a> MeshData<'a> {
+    fn new(mesh: &'a Mesh) -> io::Result<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "only triangle list meshes can be exported",
+            ));
+        }
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(values)) => values,
+            _ => return Err(invalid_attribute(Mesh::ATTRIBUTE_POSITION)),
+        };
+        let data = Self {
+            positions,
+            normals: match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+                Some(VertexAttributeValues::Float32x3(values)) => Some(values),
+                None => None,
+                _ => return Err(invalid_attribute(Mesh::ATTRIBUTE_NORMAL)),
+            },
+            colors: match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+                Some(VertexAttributeValues::Float32x4(values)) => Some(values),
+                None => None,
+                _ => return Err(invalid_attribute(Mesh::ATTRIBUTE_COLOR)),
+            },
+            uvs: match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+                Some(VertexAttributeValues::Float32x2(values)) => Some(values),
+                None => None,
+                _ => return Err(invalid_attribute(Mesh::ATTRIBUTE_UV_0)),
+            },
+            indices: mesh.indices(),
+        };
+        if data.indices.is_none() && !data.positions.len().is_multiple_of(3) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "vertex count isn't a multiple of 3",
+            ));
+        }
+        Ok(data)
+    }
+
+    fn triangle_indices(&self) -> Vec<usize> {
+        match self.indices {
+            Some(indices) => indices.iter().collect(),
+            None => (0..self.positions.len()).collect(),
+        }
+    }
+}
+
+fn invalid_attribute(attribute: MeshVertexAttribute) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unsupported {} attribute format", attribute.name),
+    )
+}
+
+/// Writes a mesh as Wavefront OBJ.
+///
+/// Vertex colors are written after the positions as `v x y z r g b`, which Blender and MeshLab
+/// import. Texture coordinates are flipped to OBJ's bottom left origin.
+pub fn write_obj(mesh: &Mesh, w: &mut impl Write) -> io::Result<()> {
+    let data = MeshData::new(mesh)?;
+    writeln!(w, "# bevy_sk hand mesh")?;
+    for (i, [x, y, z]) in data.positions.iter().enumerate() {
+        match data.colors.and_then(|colors| colors.get(i)) {
+            Some([r, g, b, _]) => writeln!(w, "v {x} {y} {z} {r} {g} {b}")?,
+            None => writeln!(w, "v {x} {y} {z}")?,
+        }
+    }
+    for [u, v] in data.uvs.unwrap_or_default() {
+        writeln!(w, "vt {u} {}", 1.0 - v)?;
+    }
+    for [x, y, z] in data.normals.unwrap_or_default() {
+        writeln!(w, "vn {x} {y} {z}")?;
+    }
+    for triangle in data.triangle_indices().chunks_exact(3) {
+        write!(w, "f")?;
+        for index in triangle {
+            // OBJ indices start at 1
+            let i = index + 1;
+            match (data.uvs.is_some(), data.normals.is_some()) {
+                (true, true) => write!(w, " {i}/{i}/{i}")?,
+                (true, false) => write!(w, " {i}/{i}")?,
+                (false, true) => write!(w, " {i}//{i}")?,
+                (false, false) => write!(w, " {i}")?,
+            }
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+const GLTF_FLOAT: u32 = 5126;
+const GLTF_UNSIGNED_SHORT: u32 = 5123;
+const GLTF_UNSIGNED_INT: u32 = 5125;
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+const GLTF_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+#[derive(Default)]
+struct GltfBuffer {
+    data: Vec<u8>,
+    views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl GltfBuffer {
+    /// Appends a buffer view and an accessor over it, returning the accessor index.
+    fn push(&mut self, bytes: &[u8], target: u32, accessor: String) -> usize {
+        // Keep every view aligned to its components
+        self.data.resize(self.data.len().next_multiple_of(4), 0);
+        self.views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{target}}}"#,
+            self.data.len(),
+            bytes.len(),
+        ));
+        self.data.extend_from_slice(bytes);
+        let view = self.views.len() - 1;
+        self.accessors
+            .push(format!(r#"{{"bufferView":{view},{accessor}}}"#));
+        self.accessors.len() - 1
+    }
+
+    fn push_floats<const N: usize>(&mut self, values: &[[f32; N]], bounds: bool) -> usize {
+        let bytes = values
+            .iter()
+            .flatten()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut accessor = format!(
+            r#""componentType":{GLTF_FLOAT},"count":{},"type":"VEC{N}""#,
+            values.len()
+        );
+        // glTF requires bounds on positions
+        if bounds {
+            let mut min = [f32::INFINITY; N];
+            let mut max = [f32::NEG_INFINITY; N];
+            for value in values {
+                for i in 0..N {
+                    min[i] = min[i].min(value[i]);
+                    max[i] = max[i].max(value[i]);
+                }
+            }
+            let _ = write!(accessor, r#","min":{min:?},"max":{max:?}"#);
+        }
+        self.push(&bytes, GLTF_ARRAY_BUFFER, accessor)
+    }
+}
+
+/// Writes a mesh as a self contained glTF 2.0 file, with a single node holding the mesh.
+pub fn write_gltf(mesh: &Mesh, w: &mut impl Write) -> io::Result<()> {
+    let data = MeshData::new(mesh)?;
+    if data.positions.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "mesh is empty"));
+    }
+    // The position bounds are written as JSON, which has no NaN or infinity
+    if !data.positions.iter().flatten().all(|v| v.is_finite()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "mesh has non-finite positions",
+        ));
+    }
+    let mut buffer = GltfBuffer::default();
+    let mut attributes = vec![format!(
+        r#""POSITION":{}"#,
+        buffer.push_floats(data.positions, true)
+    )];
+    if let Some(normals) = data.normals {
+        // glTF requires unit normals, which the hand mesh's aren't
+        let normals = normals
+            .iter()
+            .map(|normal| Vec3::from(*normal).normalize_or(Vec3::Y).to_array())
+            .collect::<Vec<_>>();
+        let accessor = buffer.push_floats(&normals, false);
+        attributes.push(format!(r#""NORMAL":{accessor}"#));
+    }
+    if let Some(colors) = data.colors {
+        let accessor = buffer.push_floats(colors, false);
+        attributes.push(format!(r#""COLOR_0":{accessor}"#));
+    }
+    if let Some(uvs) = data.uvs {
+        let accessor = buffer.push_floats(uvs, false);
+        attributes.push(format!(r#""TEXCOORD_0":{accessor}"#));
+    }
+    let indices = data.indices.map(|indices| {
+        let (bytes, component_type) = match indices {
+            Indices::U16(values) => (
+                values
+                    .iter()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect::<Vec<_>>(),
+                GLTF_UNSIGNED_SHORT,
+            ),
+            Indices::U32(values) => (
+                values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+                GLTF_UNSIGNED_INT,
+            ),
+        };
+        let accessor = format!(
+            r#""componentType":{component_type},"count":{},"type":"SCALAR""#,
+            indices.len()
+        );
+        buffer.push(&bytes, GLTF_ELEMENT_ARRAY_BUFFER, accessor)
+    });
+
+    let mut primitive = format!(r#""attributes":{{{}}},"mode":4"#, attributes.join(","));
+    if let Some(indices) = indices {
+        let _ = write!(primitive, r#","indices":{indices}"#);
+    }
+    write!(
+        w,
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"bevy_sk"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"#,
+            r#""nodes":[{{"name":"hand","mesh":0}}],"#,
+            r#""meshes":[{{"primitives":[{{{}}}]}}],"#,
+            r#""accessors":[{}],"bufferViews":[{}],"#,
+            r#""buffers":[{{"byteLength":{},"uri":"data:application/octet-stream;base64,{}"}}]}}"#,
+        ),
+        primitive,
+        buffer.accessors.join(","),
+        buffer.views.join(","),
+        buffer.data.len(),
+        base64(&buffer.data),
+    )?;
+    writeln!(w)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(ALPHABET[(n >> (18 - i * 6)) as usize & 63] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use bevy::render::render_asset::RenderAssetUsages;
+    use serde_json::Value;
+
+    use super::*;
+
+    fn quad() -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 2.0, 0.0],
+                [0.0, 2.0, -1.0],
+            ],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 2.0]; 4])
+        .with_inserted_indices(Indices::U16(vec![0, 1, 2, 0, 2, 3]))
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        let bytes = (0..=255).rev().collect::<Vec<u8>>();
+        for len in 0..=6 {
+            let encoded = base64(&bytes[..len]);
+            assert_eq!(encoded, STANDARD.encode(&bytes[..len]));
+            assert_eq!(STANDARD.decode(encoded).unwrap(), &bytes[..len]);
+        }
+    }
+
+    #[test]
+    fn gltf_accessors() {
+        let mut out = Vec::new();
+        write_gltf(&quad(), &mut out).unwrap();
+        let gltf: Value = serde_json::from_slice(&out).unwrap();
+
+        let accessors = gltf["accessors"].as_array().unwrap();
+        let views = gltf["bufferViews"].as_array().unwrap();
+        let attributes = &gltf["meshes"][0]["primitives"][0]["attributes"];
+        let position = &accessors[attributes["POSITION"].as_u64().unwrap() as usize];
+        assert_eq!(position["count"], 4);
+        assert_eq!(position["min"], serde_json::json!([0.0, 0.0, -1.0]));
+        assert_eq!(position["max"], serde_json::json!([1.0, 2.0, 0.0]));
+        let indices = &accessors[gltf["meshes"][0]["primitives"][0]["indices"]
+            .as_u64()
+            .unwrap() as usize];
+        assert_eq!(indices["count"], 6);
+        assert_eq!(indices["componentType"], GLTF_UNSIGNED_SHORT);
+
+        // Every view fits in the buffer, which decodes to its declared length
+        let buffer = &gltf["buffers"][0];
+        let uri = buffer["uri"].as_str().unwrap();
+        let data = STANDARD
+            .decode(
+                uri.strip_prefix("data:application/octet-stream;base64,")
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(buffer["byteLength"], data.len());
+        for view in views {
+            let end = view["byteOffset"].as_u64().unwrap() + view["byteLength"].as_u64().unwrap();
+            assert!(end as usize <= data.len());
+        }
+        let view = &views[position["bufferView"].as_u64().unwrap() as usize];
+        assert_eq!(view["byteLength"], 4 * 3 * 4);
+
+        // Normals are written unit length
+        let normal = &views[accessors[attributes["NORMAL"].as_u64().unwrap() as usize]["bufferView"]
+            .as_u64()
+            .unwrap() as usize];
+        let offset = normal["byteOffset"].as_u64().unwrap() as usize;
+        let z = f32::from_le_bytes(data[offset + 8..offset + 12].try_into().unwrap());
+        assert_eq!(z, 1.0);
+    }
+
+    #[test]
+    fn gltf_rejects_non_finite_positions() {
+        let mut mesh = quad();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[f32::NAN, 0.0, 0.0]; 4]);
+        let err = write_gltf(&mesh, &mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn obj_faces() {
+        let mut out = Vec::new();
+        write_obj(&quad(), &mut out).unwrap();
+        let obj = String::from_utf8(out).unwrap();
+        let faces = obj
+            .lines()
+            .filter(|l| l.starts_with("f "))
+            .collect::<Vec<_>>();
+        assert_eq!(faces, ["f 1//1 2//2 3//3", "f 1//1 3//3 4//4"]);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 4);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("vn ")).count(), 4);
+    }
+}
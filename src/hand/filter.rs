@@ -0,0 +1,211 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HAND_JOINT_COUNT;
+
+use super::{HandJoint, HandJoints};
+
+/// Tunables of a One Euro filter, which smooths heavily while still and lightly while moving.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OneEuroSettings {
+    /// Cutoff frequency in Hz while still, lower values remove more jitter.
+    pub min_cutoff: f32,
+    /// How quickly the cutoff rises with speed, higher values reduce lag.
+    pub beta: f32,
+    /// Cutoff frequency in Hz used to smooth the speed estimate.
+    pub derivative_cutoff: f32,
+}
+
+/// Opt-in filtering of a hand's [`HandJoints`], insert it on a hand entity to enable.
+///
+/// Filtering happens in place, so the mesh, gestures and everything else read the filtered
+/// joints.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[require(HandJointFilterState)]
+pub struct HandJointFilter {
+    /// Filter on joint positions, speeds are in meters per second.
+    pub position: OneEuroSettings,
+    /// Filter on joint orientations, speeds are in radians per second.
+    pub orientation: OneEuroSettings,
+}
+
+impl Default for HandJointFilter {
+    fn default() -> Self {
+        Self {
+            position: OneEuroSettings {
+                min_cutoff: 1.0,
+                beta: 20.0,
+                derivative_cutoff: 1.0,
+            },
+            orientation: OneEuroSettings {
+                min_cutoff: 1.0,
+                beta: 1.0,
+                derivative_cutoff: 1.0,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FilteredJoint {
+    position: Vec3,
+    velocity: Vec3,
+    orientation: Quat,
+    angular_speed: f32,
+}
+
+#[derive(Component, Default)]
+pub(crate) struct HandJointFilterState {
+    /// `None` for joints that were not tracked last frame, so they snap when tracking resumes.
+    joints: [Option<FilteredJoint>; HAND_JOINT_COUNT],
+}
+
+impl FilteredJoint {
+    /// Filters the next sample of the joint, `delta` seconds after the previous one.
+    fn step(&self, joint: &HandJoint, filter: &HandJointFilter, delta: f32) -> Self {
+        let settings = filter.position;
+        let velocity = self.velocity.lerp(
+            (joint.position - self.position) / delta,
+            smoothing(settings.derivative_cutoff, delta),
+        );
+        let cutoff = settings.min_cutoff + settings.beta * velocity.length();
+        let position = self.position.lerp(joint.position, smoothing(cutoff, delta));
+
+        let settings = filter.orientation;
+        let angular_speed = self.angular_speed.lerp(
+            self.orientation.angle_between(joint.orientation) / delta,
+            smoothing(settings.derivative_cutoff, delta),
+        );
+        let cutoff = settings.min_cutoff + settings.beta * angular_speed;
+        let orientation = self
+            .orientation
+            .slerp(joint.orientation, smoothing(cutoff, delta));
+
+        Self {
+            position,
+            velocity,
+            orientation,
+            angular_speed,
+        }
+    }
+}
+
+/// Smoothing factor of a low pass filter with the given cutoff frequency.
+fn smoothing(cutoff: f32, delta: f32) -> f32 {
+    let tau = 1.0 / (TAU * cutoff);
+    1.0 / (1.0 + tau / delta)
+}
+
+pub(crate) fn filter_hand_joints(
+    time: Res<Time>,
+    mut hands: Query<(&mut HandJoints, &HandJointFilter, &mut HandJointFilterState)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+    for (mut joints, filter, mut state) in &mut hands {
+        let joints = joints.as_mut();
+        for ((joint, tracked), filtered) in joints
+            .joints
+            .iter_mut()
+            .zip(joints.tracked)
+            .zip(&mut state.joints)
+        {
+            if !tracked {
+                *filtered = None;
+                continue;
+            }
+            let Some(previous) = filtered else {
+                *filtered = Some(FilteredJoint {
+                    position: joint.position,
+                    velocity: Vec3::ZERO,
+                    orientation: joint.orientation,
+                    angular_speed: 0.0,
+                });
+                continue;
+            };
+
+            *previous = previous.step(joint, filter, delta);
+            joint.position = previous.position;
+            joint.orientation = previous.orientation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELTA: f32 = 1.0 / 90.0;
+
+    fn still(position: Vec3) -> FilteredJoint {
+        FilteredJoint {
+            position,
+            velocity: Vec3::ZERO,
+            orientation: Quat::IDENTITY,
+            angular_speed: 0.0,
+        }
+    }
+
+    fn joint(position: Vec3) -> HandJoint {
+        HandJoint {
+            position,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn smoothing_follows_cutoff() {
+        let low = smoothing(1.0, DELTA);
+        let high = smoothing(10.0, DELTA);
+        assert!(0.0 < low && low < high && high < 1.0);
+    }
+
+    #[test]
+    fn removes_jitter_while_still() {
+        let filter = HandJointFilter::default();
+        let mut filtered = still(Vec3::ZERO);
+        let mut max_offset = 0.0f32;
+        for i in 0..200 {
+            let noise = if i % 2 == 0 { 0.002 } else { -0.002 };
+            filtered = filtered.step(&joint(Vec3::X * noise), &filter, DELTA);
+            max_offset = max_offset.max(filtered.position.x.abs());
+        }
+        assert!(max_offset < 0.0005);
+    }
+
+    #[test]
+    fn converges_on_a_still_target() {
+        let filter = HandJointFilter::default();
+        let target = HandJoint {
+            position: Vec3::new(0.1, 0.2, 0.3),
+            orientation: Quat::from_rotation_y(1.0),
+            radius: 0.0,
+        };
+        let mut filtered = still(Vec3::ZERO);
+        for _ in 0..500 {
+            filtered = filtered.step(&target, &filter, DELTA);
+        }
+        assert!(filtered.position.distance(target.position) < 1e-4);
+        assert!(filtered.orientation.angle_between(target.orientation) < 1e-3);
+    }
+
+    #[test]
+    fn speed_reduces_lag() {
+        let mut filter = HandJointFilter::default();
+        let lag = |filter: &HandJointFilter| {
+            let mut filtered = still(Vec3::ZERO);
+            let mut position = Vec3::ZERO;
+            for _ in 0..30 {
+                position.x += 1.0 * DELTA;
+                filtered = filtered.step(&joint(position), filter, DELTA);
+            }
+            position.x - filtered.position.x
+        };
+        let adaptive = lag(&filter);
+        filter.position.beta = 0.0;
+        let fixed = lag(&filter);
+        assert!(adaptive < fixed * 0.5);
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HandBone;
+
+use super::HandJoints;
+
+/// Activation levels at which a gesture starts and stops, giving it hysteresis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureThresholds {
+    pub activate: f32,
+    pub release: f32,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandGestureSettings {
+    pub pinch: GestureThresholds,
+    pub grip: GestureThresholds,
+    pub point: GestureThresholds,
+    /// Gap between the thumb and index fingertip surfaces at which pinch is fully active.
+    pub pinch_closed_distance: f32,
+    /// Gap between the thumb and index fingertip surfaces at which pinch has no activation.
+    pub pinch_open_distance: f32,
+    /// Fingertip to palm distance at which a finger counts as fully curled.
+    pub curl_closed_distance: f32,
+    /// Fingertip to palm distance at which a finger counts as fully extended.
+    pub curl_open_distance: f32,
+}
+
+impl Default for HandGestureSettings {
+    fn default() -> Self {
+        Self {
+            pinch: GestureThresholds {
+                activate: 0.85,
+                release: 0.65,
+            },
+            grip: GestureThresholds {
+                activate: 0.8,
+                release: 0.5,
+            },
+            point: GestureThresholds {
+                activate: 0.8,
+                release: 0.5,
+            },
+            pinch_closed_distance: 0.0,
+            pinch_open_distance: 0.05,
+            curl_closed_distance: 0.04,
+            curl_open_distance: 0.09,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GestureState {
+    /// How strongly the gesture is being made, from 0 to 1.
+    pub amount: f32,
+    pub active: bool,
+    pub just_activated: bool,
+    pub just_released: bool,
+}
+
+impl GestureState {
+    fn update(&mut self, amount: f32, thresholds: GestureThresholds) {
+        let was_active = self.active;
+        self.amount = amount;
+        self.active = match was_active {
+            true => amount > thresholds.release,
+            false => amount >= thresholds.activate,
+        };
+        self.just_activated = self.active && !was_active;
+        self.just_released = !self.active && was_active;
+    }
+}
+
+/// Pinch, grip and point state of a hand, updated from its [`HandJoints`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandGestureState {
+    pub pinch: GestureState,
+    pub grip: GestureState,
+    pub point: GestureState,
+    /// Midpoint between the thumb and index fingertips.
+    pub pinch_point: Vec3,
+}
+
+impl HandGestureState {
+    pub fn get(&self, gesture: HandGesture) -> &GestureState {
+        match gesture {
+            HandGesture::Pinch => &self.pinch,
+            HandGesture::Grip => &self.grip,
+            HandGesture::Point => &self.point,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HandGesture {
+    Pinch,
+    Grip,
+    Point,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GesturePhase {
+    Activated,
+    Released,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HandGestureEvent {
+    pub hand: Entity,
+    pub gesture: HandGesture,
+    pub phase: GesturePhase,
+}
+
+pub(crate) fn update_hand_gestures(
+    settings: Res<HandGestureSettings>,
+    mut hands: Query<(Entity, &HandJoints, &mut HandGestureState)>,
+    mut events: EventWriter<HandGestureEvent>,
+) {
+    for (hand, joints, mut state) in &mut hands {
+        let thumb = joints.get(HandBone::ThumbTip);
+        let index = joints.get(HandBone::IndexTip);
+        state.pinch_point = thumb.position.lerp(index.position, 0.5);
+
+        let curl = |tip: HandBone| {
+            let distance = joints
+                .get(tip)
+                .position
+                .distance(joints.get(HandBone::Palm).position);
+            inverse_lerp(
+                settings.curl_open_distance,
+                settings.curl_closed_distance,
+                distance,
+            )
+        };
+
+        let (pinch, grip, point) = match tracked(joints) {
+            true => {
+                let gap = thumb.position.distance(index.position) - thumb.radius - index.radius;
+                let pinch = inverse_lerp(
+                    settings.pinch_open_distance,
+                    settings.pinch_closed_distance,
+                    gap,
+                );
+                let grip = (curl(HandBone::MiddleTip)
+                    + curl(HandBone::RingTip)
+                    + curl(HandBone::LittleTip))
+                    / 3.0;
+                let point = f32::min(1.0 - curl(HandBone::IndexTip), grip);
+                (pinch, grip, point)
+            }
+            false => (0.0, 0.0, 0.0),
+        };
+
+        state.pinch.update(pinch, settings.pinch);
+        state.grip.update(grip, settings.grip);
+        state.point.update(point, settings.point);
+
+        for gesture in [HandGesture::Pinch, HandGesture::Grip, HandGesture::Point] {
+            let gesture_state = state.get(gesture);
+            let phase = match (gesture_state.just_activated, gesture_state.just_released) {
+                (true, _) => GesturePhase::Activated,
+                (_, true) => GesturePhase::Released,
+                _ => continue,
+            };
+            events.write(HandGestureEvent {
+                hand,
+                gesture,
+                phase,
+            });
+        }
+    }
+}
+
+fn tracked(joints: &HandJoints) -> bool {
+    [
+        HandBone::Palm,
+        HandBone::ThumbTip,
+        HandBone::IndexTip,
+        HandBone::MiddleTip,
+        HandBone::RingTip,
+        HandBone::LittleTip,
+    ]
+    .into_iter()
+    .all(|bone| joints.is_tracked(bone))
+}
+
+fn inverse_lerp(from: f32, to: f32, value: f32) -> f32 {
+    ((value - from) / (to - from)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: GestureThresholds = GestureThresholds {
+        activate: 0.8,
+        release: 0.5,
+    };
+
+    #[test]
+    fn hysteresis() {
+        let mut state = GestureState::default();
+        state.update(0.79, THRESHOLDS);
+        assert!(!state.active && !state.just_activated);
+
+        state.update(0.8, THRESHOLDS);
+        assert!(state.active && state.just_activated);
+        state.update(0.9, THRESHOLDS);
+        assert!(state.active && !state.just_activated);
+
+        // Stays active between the thresholds
+        state.update(0.6, THRESHOLDS);
+        assert!(state.active && !state.just_released);
+        state.update(0.5, THRESHOLDS);
+        assert!(!state.active && state.just_released);
+        state.update(0.4, THRESHOLDS);
+        assert!(!state.active && !state.just_released);
+
+        // And inactive between them, until reaching the activation level again
+        state.update(0.7, THRESHOLDS);
+        assert!(!state.active && !state.just_activated);
+        state.update(1.0, THRESHOLDS);
+        assert!(state.active && state.just_activated);
+        assert_eq!(state.amount, 1.0);
+    }
+
+    #[test]
+    fn inverse_lerp_clamps() {
+        assert_eq!(inverse_lerp(0.05, 0.0, 0.1), 0.0);
+        assert_eq!(inverse_lerp(0.05, 0.0, 0.025), 0.5);
+        assert_eq!(inverse_lerp(0.05, 0.0, -0.01), 1.0);
+    }
+}
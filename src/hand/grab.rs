@@ -0,0 +1,342 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HandBone;
+
+use super::HandJoints;
+use super::gestures::{GestureState, HandGestureState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GrabGesture {
+    /// Grabbed at the pinch point between the thumb and index fingertips.
+    Pinch,
+    /// Grabbed at the palm with a closed hand.
+    Grip,
+    Any,
+}
+
+/// Lets hands pick up and move an entity, and with both hands rotate and scale it.
+///
+/// The entity is moved in world space, parents are taken into account.
+#[derive(Component, Clone, Copy, Debug)]
+#[require(Transform, GrabState)]
+pub struct Grabbable {
+    pub gesture: GrabGesture,
+    /// How close the grab point has to be to the entity's origin, scaled with the entity.
+    pub radius: f32,
+    /// World axes the entity can't move along.
+    pub lock_translation: BVec3,
+    /// Axes the entity can't rotate around, as yaw (Y), pitch (X) and roll (Z) of the rotation
+    /// since the grab started.
+    pub lock_rotation: BVec3,
+    /// Whether pulling two hands apart scales the entity.
+    pub allow_scale: bool,
+    /// Bounds of the entity's largest scale axis while scaling.
+    pub min_scale: f32,
+    pub max_scale: f32,
+    /// How quickly the entity follows the hands, higher values smooth less.
+    pub smoothing_speed: f32,
+}
+
+impl Default for Grabbable {
+    fn default() -> Self {
+        Self {
+            gesture: GrabGesture::Any,
+            radius: 0.1,
+            lock_translation: BVec3::FALSE,
+            lock_rotation: BVec3::FALSE,
+            allow_scale: true,
+            min_scale: 0.1,
+            max_scale: 10.0,
+            smoothing_speed: 30.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct GrabHand {
+    hand: Entity,
+    gesture: GrabGesture,
+    /// Hand pose when the grab was last anchored.
+    start: Transform,
+}
+
+/// The hands holding a [`Grabbable`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct GrabState {
+    hands: Vec<GrabHand>,
+    /// World pose of the entity when the grab was last anchored.
+    start: Transform,
+}
+
+impl GrabState {
+    pub fn is_grabbed(&self) -> bool {
+        !self.hands.is_empty()
+    }
+
+    pub fn hands(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.hands.iter().map(|h| h.hand)
+    }
+}
+
+/// The [`Grabbable`] a hand is holding.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandGrabState {
+    pub held: Option<Entity>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GrabPhase {
+    Started,
+    Ended,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GrabEvent {
+    pub hand: Entity,
+    pub target: Entity,
+    pub phase: GrabPhase,
+}
+
+/// Where a hand grabs with a gesture, oriented like the palm.
+fn grab_pose(joints: &HandJoints, gestures: &HandGestureState, gesture: GrabGesture) -> Transform {
+    let palm = joints.get(HandBone::Palm);
+    let translation = match gesture {
+        GrabGesture::Grip => palm.position,
+        _ => gestures.pinch_point,
+    };
+    Transform::from_translation(translation).with_rotation(palm.orientation)
+}
+
+fn gesture_state(gestures: &HandGestureState, gesture: GrabGesture) -> &GestureState {
+    match gesture {
+        GrabGesture::Grip => &gestures.grip,
+        _ => &gestures.pinch,
+    }
+}
+
+fn hand_tracked(joints: &HandJoints) -> bool {
+    [HandBone::Palm, HandBone::ThumbTip, HandBone::IndexTip]
+        .into_iter()
+        .all(|bone| joints.is_tracked(bone))
+}
+
+fn constrain_rotation(rotation: Quat, lock: BVec3) -> Quat {
+    if lock == BVec3::FALSE {
+        return rotation;
+    }
+    let (y, x, z) = rotation.to_euler(EulerRot::YXZ);
+    Quat::from_euler(
+        EulerRot::YXZ,
+        if lock.y { 0.0 } else { y },
+        if lock.x { 0.0 } else { x },
+        if lock.z { 0.0 } else { z },
+    )
+}
+
+/// Restarts the grab from the current poses, so the entity doesn't jump when hands join or leave.
+fn reanchor(
+    state: &mut GrabState,
+    global: &GlobalTransform,
+    hands: &Query<(Entity, &HandJoints, &HandGestureState)>,
+) {
+    for grab in &mut state.hands {
+        if let Ok((_, joints, gestures)) = hands.get(grab.hand) {
+            grab.start = grab_pose(joints, gestures, grab.gesture);
+        }
+    }
+    state.start = global.compute_transform();
+}
+
+/// Lets go of what a hand held when it goes away, as [`update_grabs`] only sees existing hands.
+pub(crate) fn release_removed_hand(
+    trigger: Trigger<OnRemove, HandJoints>,
+    hand_grabs: Query<&HandGrabState>,
+    hands: Query<(Entity, &HandJoints, &HandGestureState)>,
+    mut grabbables: Query<(&mut GrabState, &GlobalTransform)>,
+    mut events: EventWriter<GrabEvent>,
+) {
+    let hand = trigger.target();
+    let Some(held) = hand_grabs.get(hand).ok().and_then(|grab| grab.held) else {
+        return;
+    };
+    let Ok((mut state, global)) = grabbables.get_mut(held) else {
+        return;
+    };
+    state.hands.retain(|h| h.hand != hand);
+    reanchor(&mut state, global, &hands);
+    events.write(GrabEvent {
+        hand,
+        target: held,
+        phase: GrabPhase::Ended,
+    });
+}
+
+#[expect(clippy::type_complexity)]
+pub(crate) fn update_grabs(
+    time: Res<Time>,
+    hands: Query<(Entity, &HandJoints, &HandGestureState)>,
+    mut hand_grabs: Query<&mut HandGrabState>,
+    mut grabbables: Query<(
+        Entity,
+        &Grabbable,
+        &mut GrabState,
+        &mut Transform,
+        &GlobalTransform,
+        Option<&ChildOf>,
+    )>,
+    parents: Query<&GlobalTransform>,
+    mut events: EventWriter<GrabEvent>,
+) {
+    // Let go of released grabs
+    for (hand, joints, gestures) in &hands {
+        let Ok(mut grab) = hand_grabs.get_mut(hand) else {
+            continue;
+        };
+        let Some(held) = grab.held else {
+            continue;
+        };
+        let Ok((_, _, mut state, _, global, _)) = grabbables.get_mut(held) else {
+            grab.held = None;
+            continue;
+        };
+        let Some(index) = state.hands.iter().position(|h| h.hand == hand) else {
+            grab.held = None;
+            continue;
+        };
+        let gesture = state.hands[index].gesture;
+        if hand_tracked(joints) && gesture_state(gestures, gesture).active {
+            continue;
+        }
+        state.hands.remove(index);
+        reanchor(&mut state, global, &hands);
+        grab.held = None;
+        events.write(GrabEvent {
+            hand,
+            target: held,
+            phase: GrabPhase::Ended,
+        });
+    }
+
+    // Start new grabs on the closest grabbable in reach
+    for (hand, joints, gestures) in &hands {
+        let Ok(mut grab) = hand_grabs.get_mut(hand) else {
+            continue;
+        };
+        if grab.held.is_some() || !hand_tracked(joints) {
+            continue;
+        }
+        let closest = grabbables
+            .iter()
+            .filter(|(_, _, state, ..)| state.hands.len() < 2)
+            .filter_map(|(e, grabbable, _, _, global, _)| {
+                let gesture = match grabbable.gesture {
+                    GrabGesture::Any if gestures.grip.just_activated => GrabGesture::Grip,
+                    GrabGesture::Any => GrabGesture::Pinch,
+                    gesture => gesture,
+                };
+                if !gesture_state(gestures, gesture).just_activated {
+                    return None;
+                }
+                let (scale, _, translation) = global.to_scale_rotation_translation();
+                let distance = grab_pose(joints, gestures, gesture)
+                    .translation
+                    .distance(translation);
+                (distance <= grabbable.radius * scale.max_element())
+                    .then_some((e, gesture, distance))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+        let Some((target, gesture, _)) = closest else {
+            continue;
+        };
+        let Ok((_, _, mut state, _, global, _)) = grabbables.get_mut(target) else {
+            continue;
+        };
+        state.hands.push(GrabHand {
+            hand,
+            gesture,
+            start: Transform::IDENTITY,
+        });
+        reanchor(&mut state, global, &hands);
+        grab.held = Some(target);
+        events.write(GrabEvent {
+            hand,
+            target,
+            phase: GrabPhase::Started,
+        });
+    }
+
+    // Move the held entities
+    let delta = time.delta_secs();
+    for (_, grabbable, state, mut transform, global, parent) in &mut grabbables {
+        let poses = state
+            .hands
+            .iter()
+            .filter_map(|h| {
+                let (_, joints, gestures) = hands.get(h.hand).ok()?;
+                Some((h.start, grab_pose(joints, gestures, h.gesture)))
+            })
+            .collect::<Vec<_>>();
+        let start = state.start;
+        let target = match poses[..] {
+            [] => continue,
+            [(hand_start, hand)] => {
+                let turn = constrain_rotation(
+                    hand.rotation * hand_start.rotation.inverse(),
+                    grabbable.lock_rotation,
+                );
+                Transform {
+                    translation: hand.translation
+                        + turn * (start.translation - hand_start.translation),
+                    rotation: turn * start.rotation,
+                    scale: start.scale,
+                }
+            }
+            [(a_start, a), (b_start, b), ..] => {
+                let start_span = b_start.translation - a_start.translation;
+                let span = b.translation - a.translation;
+                let start_center = a_start.translation.lerp(b_start.translation, 0.5);
+                let center = a.translation.lerp(b.translation, 0.5);
+                let turn = constrain_rotation(
+                    Quat::from_rotation_arc(
+                        start_span.normalize_or(Vec3::X),
+                        span.normalize_or(Vec3::X),
+                    ),
+                    grabbable.lock_rotation,
+                );
+                let start_size = start.scale.max_element();
+                let factor = match grabbable.allow_scale && start_span.length() > 0.0 {
+                    true => (span.length() / start_span.length()).clamp(
+                        grabbable.min_scale / start_size,
+                        grabbable.max_scale / start_size,
+                    ),
+                    false => 1.0,
+                };
+                Transform {
+                    translation: center + turn * (start.translation - start_center) * factor,
+                    rotation: turn * start.rotation,
+                    scale: start.scale * factor,
+                }
+            }
+        };
+        let target = Transform {
+            translation: Vec3::select(
+                grabbable.lock_translation,
+                start.translation,
+                target.translation,
+            ),
+            ..target
+        };
+
+        let current = global.compute_transform();
+        let blend = 1.0 - (-grabbable.smoothing_speed * delta).exp();
+        let world = GlobalTransform::from(Transform {
+            translation: current.translation.lerp(target.translation, blend),
+            rotation: current.rotation.slerp(target.rotation, blend),
+            scale: current.scale.lerp(target.scale, blend),
+        });
+        let parent = parent.and_then(|parent| parents.get(parent.parent()).ok());
+        *transform = match parent {
+            Some(parent) => world.reparented_to(parent),
+            None => world.compute_transform(),
+        };
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::asset::weak_handle;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{AsBindGroup, AsBindGroupShaderType, ShaderRef, ShaderType};
+use bevy::render::texture::GpuImage;
+use bevy_mod_xr::hands::HandBone;
+
+use super::{Finger, FingerJoint, HandJoints};
+
+pub const HAND_SHADER_HANDLE: Handle<Shader> = weak_handle!("3c1f0b0e-52a4-4f7e-9a40-6f1d0b9c2a71");
+
+/// StereoKit style hand material, fading from the wrist to the fingertips with a fresnel rim
+/// and glowing fingertips.
+///
+/// Expects the UVs and vertex colors produced by the hand mesh.
+#[derive(Asset, AsBindGroup, PartialEq, Debug, Clone, Reflect)]
+#[uniform(0, HandMaterialUniform)]
+pub struct HandMaterial {
+    pub color: Color,
+    /// Color added at grazing angles, alpha scales its strength.
+    pub rim_color: Color,
+    /// Higher values make the rim thinner.
+    pub rim_power: f32,
+    pub glow_color: Color,
+    /// Distance from a fingertip over which its glow fades out.
+    pub glow_radius: f32,
+    /// Fingertip positions in world space, indexed by [`Finger`].
+    pub fingertips: [Vec3; Finger::NUM],
+    /// How strongly each fingertip glows, from 0 to 1.
+    pub fingertip_glow: [f32; Finger::NUM],
+    /// Wrist to fingertip fade, sampled with the mesh's UVs.
+    #[texture(1)]
+    #[sampler(2)]
+    pub gradient_texture: Option<Handle<Image>>,
+}
+
+impl Default for HandMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            rim_color: Color::srgba(1.0, 1.0, 1.0, 0.4),
+            rim_power: 3.0,
+            glow_color: Color::srgb(0.6, 0.85, 1.0),
+            glow_radius: 0.03,
+            fingertips: [Vec3::ZERO; Finger::NUM],
+            fingertip_glow: [0.0; Finger::NUM],
+            gradient_texture: None,
+        }
+    }
+}
+
+#[derive(Clone, Default, ShaderType)]
+pub struct HandMaterialUniform {
+    pub color: Vec4,
+    pub rim_color: Vec4,
+    pub glow_color: Vec4,
+    /// xyz is the fingertip position, w its glow.
+    pub fingertips: [Vec4; Finger::NUM],
+    pub rim_power: f32,
+    pub glow_radius: f32,
+}
+
+impl AsBindGroupShaderType<HandMaterialUniform> for HandMaterial {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> HandMaterialUniform {
+        let mut fingertips = [Vec4::ZERO; Finger::NUM];
+        for ((tip, position), glow) in fingertips
+            .iter_mut()
+            .zip(self.fingertips)
+            .zip(self.fingertip_glow)
+        {
+            *tip = position.extend(glow);
+        }
+        HandMaterialUniform {
+            color: self.color.to_linear().to_f32_array().into(),
+            rim_color: self.rim_color.to_linear().to_f32_array().into(),
+            glow_color: self.glow_color.to_linear().to_f32_array().into(),
+            fingertips,
+            rim_power: self.rim_power,
+            glow_radius: self.glow_radius,
+        }
+    }
+}
+
+impl Material for HandMaterial {
+    fn fragment_shader() -> ShaderRef {
+        HAND_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+/// How strongly each fingertip of a hand glows, indexed by [`Finger`].
+///
+/// Interaction systems raise this as a finger approaches something it can touch.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandFingertipGlow(pub [f32; Finger::NUM]);
+
+pub(crate) fn update_hand_material(
+    mut materials: ResMut<Assets<HandMaterial>>,
+    hands: Query<(
+        &MeshMaterial3d<HandMaterial>,
+        &HandJoints,
+        &HandFingertipGlow,
+    )>,
+) {
+    for (material, joints, glow) in &hands {
+        // Avoid touching the asset, and re-uploading it, while nothing glows
+        let glowing = glow.0.iter().any(|g| *g > 0.0);
+        let was_glowing = materials
+            .get(&material.0)
+            .is_some_and(|m| m.fingertip_glow.iter().any(|g| *g > 0.0));
+        if !glowing && !was_glowing {
+            continue;
+        }
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+        for finger in Finger::ALL {
+            let tip: HandBone = finger.hand_bone(&FingerJoint::Tip);
+            material.fingertips[finger as usize] = joints.get(tip).position;
+        }
+        material.fingertip_glow = glow.0;
+    }
+}
@@ -0,0 +1,360 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HandBone;
+
+use super::aim::HeadPose;
+use super::gestures::{HandGestureState, update_hand_gestures};
+use super::{HandJoints, HandSet, Handed};
+
+/// A radial menu that opens above a hand when its palm faces the user, and is used by pinching
+/// its items with the other hand.
+///
+/// Set the items with the [`HandMenu`] resource and listen for [`HandMenuEvent`]s. The gizmos can't
+/// draw text, so place the item labels yourself from [`HandMenuState::labels`].
+pub struct HandMenuPlugin;
+
+impl Plugin for HandMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HandMenu>();
+        app.init_resource::<HandMenuSettings>();
+        app.init_resource::<HandMenuState>();
+        app.add_event::<HandMenuEvent>();
+        app.add_systems(
+            Update,
+            (
+                update_hand_menu
+                    .after(update_hand_gestures)
+                    .in_set(HandSet::Interaction),
+                draw_hand_menu.in_set(HandSet::Visuals),
+            ),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct HandMenu {
+    pub items: Vec<HandMenuItem>,
+}
+
+#[derive(Clone, Debug)]
+pub struct HandMenuItem {
+    pub label: String,
+    pub kind: HandMenuItemKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum HandMenuItemKind {
+    /// Sends a [`HandMenuEvent`] with this id and goes back to the root layer.
+    Action(String),
+    /// Opens a nested layer of items.
+    Submenu(Vec<HandMenuItem>),
+    /// Goes back to the parent layer.
+    Back,
+}
+
+impl HandMenuItem {
+    pub fn action(label: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            kind: HandMenuItemKind::Action(id.into()),
+        }
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<HandMenuItem>) -> Self {
+        Self {
+            label: label.into(),
+            kind: HandMenuItemKind::Submenu(items),
+        }
+    }
+
+    pub fn back(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            kind: HandMenuItemKind::Back,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandMenuSettings {
+    /// Only this hand opens the menu, either hand does when `None`.
+    pub menu_hand: Option<Handed>,
+    /// How directly the palm has to face the head to open the menu, as the cosine of the angle.
+    pub facing_threshold: f32,
+    /// Seconds the palm can face away before the menu closes.
+    pub close_delay: f32,
+    /// Distance of the menu's center from the palm, along the palm's normal.
+    pub palm_offset: f32,
+    /// Pinches closer to the center than this don't select anything.
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// How far in front of or behind the menu a pinch still selects.
+    pub depth_tolerance: f32,
+    /// Draw the menu's outline with gizmos, disable to render it yourself from [`HandMenuState`].
+    pub show_gizmos: bool,
+    pub color: Color,
+    pub highlight_color: Color,
+}
+
+impl Default for HandMenuSettings {
+    fn default() -> Self {
+        Self {
+            menu_hand: None,
+            facing_threshold: 0.7,
+            close_delay: 0.3,
+            palm_offset: 0.08,
+            inner_radius: 0.02,
+            outer_radius: 0.1,
+            depth_tolerance: 0.05,
+            show_gizmos: true,
+            color: Color::WHITE,
+            highlight_color: Color::srgb(0.6, 0.85, 1.0),
+        }
+    }
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct HandMenuEvent {
+    /// The hand that pinched the item.
+    pub hand: Entity,
+    /// Id of the [`HandMenuItemKind::Action`].
+    pub id: String,
+}
+
+/// Where to show an item's label, see [`HandMenuState::labels`].
+#[derive(Clone, Copy, Debug)]
+pub struct HandMenuLabel<'a> {
+    pub text: &'a str,
+    /// Centered on the item, facing the head with +Z like the menu.
+    pub transform: Transform,
+    pub highlighted: bool,
+    pub kind: &'a HandMenuItemKind,
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct HandMenuState {
+    /// The hand the menu is open above.
+    pub menu_hand: Option<Entity>,
+    /// Center of the menu, facing the head with +Z. Items are laid out in its XY plane.
+    pub transform: Transform,
+    /// Indices of the opened submenus, from the root.
+    pub path: Vec<usize>,
+    /// The item under the other hand's pinch point.
+    pub highlighted: Option<usize>,
+    facing_away_for: f32,
+}
+
+impl HandMenuState {
+    pub fn is_open(&self) -> bool {
+        self.menu_hand.is_some()
+    }
+
+    /// Items of the currently opened layer.
+    pub fn layer<'a>(&self, menu: &'a HandMenu) -> &'a [HandMenuItem] {
+        let mut items = &menu.items[..];
+        for i in &self.path {
+            match items.get(*i).map(|item| &item.kind) {
+                Some(HandMenuItemKind::Submenu(children)) => items = children,
+                _ => return &[],
+            }
+        }
+        items
+    }
+
+    /// Labels of the currently opened layer's items, empty while the menu is closed.
+    pub fn labels<'a>(
+        &'a self,
+        menu: &'a HandMenu,
+        settings: &'a HandMenuSettings,
+    ) -> impl Iterator<Item = HandMenuLabel<'a>> {
+        let items = match self.is_open() {
+            true => self.layer(menu),
+            false => &[],
+        };
+        items.iter().enumerate().map(move |(i, item)| {
+            let position = self.item_position(i, items.len(), settings);
+            HandMenuLabel {
+                text: &item.label,
+                transform: Transform::from_translation(position)
+                    .with_rotation(self.transform.rotation),
+                highlighted: self.highlighted == Some(i),
+                kind: &item.kind,
+            }
+        })
+    }
+
+    /// World space position of an item, clockwise from the top.
+    pub fn item_position(&self, index: usize, count: usize, settings: &HandMenuSettings) -> Vec3 {
+        let angle = FRAC_PI_2 - index as f32 * TAU / count as f32;
+        let radius = (settings.inner_radius + settings.outer_radius) * 0.5;
+        self.transform
+            .transform_point(Vec2::from_angle(angle).extend(0.0) * radius)
+    }
+
+    /// The item whose slice contains `point`.
+    pub fn item_at(&self, point: Vec3, count: usize, settings: &HandMenuSettings) -> Option<usize> {
+        let local = self
+            .transform
+            .compute_affine()
+            .inverse()
+            .transform_point3(point);
+        let distance = local.truncate().length();
+        if count == 0
+            || local.z.abs() > settings.depth_tolerance
+            || distance < settings.inner_radius
+            || distance > settings.outer_radius
+        {
+            return None;
+        }
+        let step = TAU / count as f32;
+        let angle = FRAC_PI_2 - local.y.atan2(local.x);
+        Some((angle / step).round().rem_euclid(count as f32) as usize % count)
+    }
+}
+
+fn palm_facing(joints: &HandJoints, head: Vec3, settings: &HandMenuSettings) -> Option<Vec3> {
+    if !joints.is_tracked(HandBone::Palm) {
+        return None;
+    }
+    let palm = joints.get(HandBone::Palm);
+    let normal = palm.orientation * Vec3::NEG_Y;
+    let to_head = (head - palm.position).normalize_or_zero();
+    (normal.dot(to_head) >= settings.facing_threshold)
+        .then_some(palm.position + normal * settings.palm_offset)
+}
+
+fn update_hand_menu(
+    menu: Res<HandMenu>,
+    settings: Res<HandMenuSettings>,
+    mut state: ResMut<HandMenuState>,
+    time: Res<Time>,
+    head: HeadPose,
+    hands: Query<(Entity, &Handed, &HandJoints, &HandGestureState)>,
+    mut events: EventWriter<HandMenuEvent>,
+) {
+    let Some(head) = head.get() else {
+        return;
+    };
+    let state = &mut *state;
+
+    if let Some(menu_hand) = state.menu_hand {
+        let facing = hands
+            .get(menu_hand)
+            .ok()
+            .and_then(|(_, _, joints, _)| palm_facing(joints, head.translation, &settings));
+        state.facing_away_for = match facing {
+            Some(_) => 0.0,
+            None => state.facing_away_for + time.delta_secs(),
+        };
+        if state.facing_away_for > settings.close_delay {
+            *state = HandMenuState::default();
+        }
+    }
+    if state.menu_hand.is_none() {
+        let opened = hands.iter().find(|(_, handed, joints, _)| {
+            settings.menu_hand.is_none_or(|h| h == **handed)
+                && palm_facing(joints, head.translation, &settings).is_some()
+        });
+        let Some((menu_hand, ..)) = opened else {
+            return;
+        };
+        state.menu_hand = Some(menu_hand);
+    }
+    let Some(menu_hand) = state.menu_hand else {
+        return;
+    };
+
+    let pincher = hands
+        .iter()
+        .find(|(e, _, joints, _)| {
+            *e != menu_hand
+                && joints.is_tracked(HandBone::ThumbTip)
+                && joints.is_tracked(HandBone::IndexTip)
+        })
+        .map(|(e, _, _, gestures)| (e, gestures));
+
+    // Follow the palm until the other hand comes close to select something
+    let center = hands
+        .get(menu_hand)
+        .ok()
+        .and_then(|(_, _, joints, _)| palm_facing(joints, head.translation, &settings));
+    let approaching = pincher.is_some_and(|(_, gestures)| {
+        gestures.pinch_point.distance(state.transform.translation) < settings.outer_radius * 2.0
+    });
+    if let Some(center) = center.filter(|_| !approaching) {
+        state.transform =
+            Transform::from_translation(center).looking_to(center - head.translation, Vec3::Y);
+    }
+
+    let items = state.layer(&menu);
+    state.highlighted = pincher
+        .and_then(|(_, gestures)| state.item_at(gestures.pinch_point, items.len(), &settings));
+    let Some((pinch_hand, gestures)) = pincher else {
+        return;
+    };
+    let Some(item) = state.highlighted.and_then(|i| items.get(i)) else {
+        return;
+    };
+    if !gestures.pinch.just_activated {
+        return;
+    }
+    match &item.kind {
+        HandMenuItemKind::Action(id) => {
+            events.write(HandMenuEvent {
+                hand: pinch_hand,
+                id: id.clone(),
+            });
+            state.path.clear();
+        }
+        HandMenuItemKind::Submenu(_) => {
+            let index = state.highlighted.unwrap_or_default();
+            state.path.push(index);
+        }
+        HandMenuItemKind::Back => {
+            state.path.pop();
+        }
+    }
+    state.highlighted = None;
+}
+
+fn draw_hand_menu(
+    menu: Res<HandMenu>,
+    settings: Res<HandMenuSettings>,
+    state: Res<HandMenuState>,
+    mut gizmos: Gizmos,
+) {
+    if !settings.show_gizmos || !state.is_open() {
+        return;
+    }
+    let isometry = Isometry3d::new(state.transform.translation, state.transform.rotation);
+    gizmos.circle(isometry, settings.inner_radius, settings.color);
+    gizmos.circle(isometry, settings.outer_radius, settings.color);
+
+    let count = state.layer(&menu).len();
+    let step = TAU / count as f32;
+    for (i, label) in state.labels(&menu, &settings).enumerate() {
+        // Slice boundaries sit halfway between items
+        let boundary = Vec2::from_angle(FRAC_PI_2 - (i as f32 + 0.5) * step).extend(0.0);
+        gizmos.line(
+            state
+                .transform
+                .transform_point(boundary * settings.inner_radius),
+            state
+                .transform
+                .transform_point(boundary * settings.outer_radius),
+            settings.color,
+        );
+
+        let color = match label.highlighted {
+            true => settings.highlight_color,
+            false => settings.color,
+        };
+        let isometry = Isometry3d::new(label.transform.translation, label.transform.rotation);
+        let radius = settings.outer_radius * 0.12;
+        gizmos.circle(isometry, radius, color);
+        if let HandMenuItemKind::Submenu(_) = label.kind {
+            gizmos.circle(isometry, radius * 0.6, color);
+        }
+    }
+}
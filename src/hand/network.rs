@@ -0,0 +1,465 @@
+use std::collections::VecDeque;
+use std::f32::consts::FRAC_1_SQRT_2;
+use std::io::{self, Read, Write};
+
+use bevy::ecs::system::SystemParamItem;
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{HAND_JOINT_COUNT, HandBone};
+
+use super::source::{HandJointSource, HandJointSourcePlugin, UpdateHandJointsSet};
+use super::{HandJoint, HandJoints, HandSet, Handed};
+
+const VERSION: u8 = 1;
+/// Meters per unit of the quantized joint positions, covering ±0.5 m around the wrist.
+const POSITION_STEP: f32 = 1.0 / 65536.0;
+/// Meters per unit of the quantized radii, up to 51 mm.
+const RADIUS_STEP: f32 = 0.0002;
+const QUAT_COMPONENT_MAX: u32 = (1 << 10) - 1;
+
+/// Plays back hands received over the network through their [`HandPoseBuffer`].
+///
+/// Spawn an entity with a [`Handed`] and a [`HandPoseBuffer`] for each remote hand, and
+/// [`push`](HandPoseBuffer::push) the [`HandPosePacket`]s as they arrive.
+pub struct NetworkHandPlugin;
+
+impl Plugin for NetworkHandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(HandJointSourcePlugin::<HandPoseBuffer>::default());
+        app.add_systems(
+            Update,
+            advance_hand_pose_buffers
+                .before(UpdateHandJointsSet)
+                .in_set(HandSet::Joints),
+        );
+    }
+}
+
+/// A hand pose compressed for sending over the network.
+///
+/// The packet is [`HandPosePacket::SIZE`] bytes, little endian:
+/// - header: `u8` with the version (1) in the high 4 bits and bit 0 set for the left hand
+/// - `u32` sender time in milliseconds
+/// - `u32` tracked bits, bit `i` for the joint with [`HandBone`] index `i`
+/// - `[f32; 3]` wrist position
+/// - per joint except the wrist, in [`HandBone`] order, `[i16; 3]` position relative to the wrist
+///   in steps of 1/65536 m
+/// - per joint a `u32` smallest-three orientation: the index of the largest component in the
+///   high 2 bits, then the other three components in xyzw order as 10 bit values mapping
+///   0..1023 to -1/√2..1/√2, the largest component made positive
+/// - per joint a `u8` radius in steps of 0.2 mm
+#[derive(Clone, Debug)]
+pub struct HandPosePacket {
+    pub handed: Handed,
+    /// When the pose was captured in the sender's clock, wrapping after 49 days.
+    pub time_ms: u32,
+    pub joints: HandJoints,
+}
+
+impl HandPosePacket {
+    pub const SIZE: usize = 1 + 4 + 4 + 12 + (HAND_JOINT_COUNT - 1) * 6 + HAND_JOINT_COUNT * 5;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        // Writing to a Vec can't fail
+        let _ = self.write(&mut bytes);
+        bytes
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+        Self::read(&mut bytes)
+    }
+
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&[VERSION << 4 | (self.handed == Handed::Left) as u8])?;
+        w.write_all(&self.time_ms.to_le_bytes())?;
+        let tracked = self
+            .joints
+            .tracked
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, tracked)| bits | (*tracked as u32) << i);
+        w.write_all(&tracked.to_le_bytes())?;
+
+        let wrist = self.joints.get(HandBone::Wrist).position;
+        for v in wrist.to_array() {
+            w.write_all(&v.to_le_bytes())?;
+        }
+        for (i, joint) in self.joints.joints.iter().enumerate() {
+            if i == HandBone::Wrist as usize {
+                continue;
+            }
+            let offset = (joint.position - wrist) / POSITION_STEP;
+            for v in offset.to_array() {
+                let v = v.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                w.write_all(&v.to_le_bytes())?;
+            }
+        }
+        for joint in &self.joints.joints {
+            w.write_all(&encode_quat(joint.orientation).to_le_bytes())?;
+        }
+        for joint in &self.joints.joints {
+            w.write_all(&[(joint.radius / RADIUS_STEP).round().clamp(0.0, 255.0) as u8])?;
+        }
+        Ok(())
+    }
+
+    pub fn read(r: &mut impl Read) -> io::Result<Self> {
+        let [header] = read_bytes(r)?;
+        let version = header >> 4;
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported hand pose packet version {version}"),
+            ));
+        }
+        let handed = match header & 1 {
+            0 => Handed::Right,
+            _ => Handed::Left,
+        };
+        let time_ms = u32::from_le_bytes(read_bytes(r)?);
+        let tracked = u32::from_le_bytes(read_bytes(r)?);
+
+        let mut joints = HandJoints::default();
+        for (i, joint_tracked) in joints.tracked.iter_mut().enumerate() {
+            *joint_tracked = tracked & (1 << i) != 0;
+        }
+        let mut wrist = [0.0; 3];
+        for v in &mut wrist {
+            *v = f32::from_le_bytes(read_bytes(r)?);
+        }
+        let wrist = Vec3::from_array(wrist);
+        for (i, joint) in joints.joints.iter_mut().enumerate() {
+            if i == HandBone::Wrist as usize {
+                joint.position = wrist;
+                continue;
+            }
+            let mut offset = [0.0; 3];
+            for v in &mut offset {
+                *v = i16::from_le_bytes(read_bytes(r)?) as f32;
+            }
+            joint.position = wrist + Vec3::from_array(offset) * POSITION_STEP;
+        }
+        for joint in &mut joints.joints {
+            joint.orientation = decode_quat(u32::from_le_bytes(read_bytes(r)?));
+        }
+        for joint in &mut joints.joints {
+            let [radius] = read_bytes(r)?;
+            joint.radius = radius as f32 * RADIUS_STEP;
+        }
+        Ok(Self {
+            handed,
+            time_ms,
+            joints,
+        })
+    }
+}
+
+fn read_bytes<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    r.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn encode_quat(q: Quat) -> u32 {
+    let q = q.normalize().to_array();
+    let largest = (0..4)
+        .max_by(|a, b| q[*a].abs().total_cmp(&q[*b].abs()))
+        .unwrap_or_default();
+    // q and -q are the same rotation, so the largest component can always be positive
+    let sign = q[largest].signum();
+    let mut bits = largest as u32;
+    for (_, v) in q.iter().enumerate().filter(|(i, _)| *i != largest) {
+        let normalized = (v * sign / FRAC_1_SQRT_2 * 0.5 + 0.5).clamp(0.0, 1.0);
+        bits = bits << 10 | (normalized * QUAT_COMPONENT_MAX as f32).round() as u32;
+    }
+    bits
+}
+
+fn decode_quat(bits: u32) -> Quat {
+    let largest = (bits >> 30) as usize;
+    let mut q = [0.0; 4];
+    let mut shift = 30;
+    for (i, v) in q.iter_mut().enumerate() {
+        if i == largest {
+            continue;
+        }
+        shift -= 10;
+        let normalized = ((bits >> shift) & QUAT_COMPONENT_MAX) as f32 / QUAT_COMPONENT_MAX as f32;
+        *v = (normalized - 0.5) * 2.0 * FRAC_1_SQRT_2;
+    }
+    q[largest] = (1.0 - q.iter().map(|v| v * v).sum::<f32>()).max(0.0).sqrt();
+    Quat::from_array(q).normalize()
+}
+
+/// Received [`HandPosePacket`]s of a remote hand, played back with a small delay so there's
+/// usually a later pose to interpolate towards.
+#[derive(Component, Clone, Debug)]
+pub struct HandPoseBuffer {
+    /// Seconds playback stays behind the newest packet, longer delays hide more jitter and
+    /// packet loss.
+    pub delay: f32,
+    /// Playback jumps to the delayed time when it drifts further than this many seconds from it,
+    /// otherwise it catches up smoothly. Packets this far behind playback mean the sender's clock
+    /// restarted or wrapped, and replace the buffered ones.
+    pub resync_threshold: f32,
+    /// Seconds without new packets after which the hand counts as lost.
+    pub timeout: f32,
+    /// Sorted by time.
+    packets: VecDeque<HandPosePacket>,
+    /// Playback time in the sender's clock, in milliseconds.
+    playback_ms: Option<f64>,
+    /// Seconds since the last packet arrived, in our clock.
+    since_last_packet: f32,
+}
+
+impl Default for HandPoseBuffer {
+    fn default() -> Self {
+        Self {
+            delay: 0.1,
+            resync_threshold: 0.5,
+            timeout: 0.5,
+            packets: VecDeque::new(),
+            playback_ms: None,
+            since_last_packet: 0.0,
+        }
+    }
+}
+
+impl HandPoseBuffer {
+    /// Adds a received packet, ignoring it if it arrived too late to be played back.
+    pub fn push(&mut self, packet: HandPosePacket) {
+        if let Some(playback) = self.playback_ms {
+            let behind = playback - packet.time_ms as f64;
+            if behind > self.resync_threshold as f64 * 1000.0 {
+                self.clear();
+            } else if behind > 0.0 {
+                return;
+            }
+        }
+        self.since_last_packet = 0.0;
+        let index = self.packets.partition_point(|p| p.time_ms < packet.time_ms);
+        match self.packets.get(index) {
+            Some(p) if p.time_ms == packet.time_ms => {}
+            _ => self.packets.insert(index, packet),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.packets.clear();
+        self.playback_ms = None;
+        self.since_last_packet = 0.0;
+    }
+
+    /// The joints at a time in the sender's clock, holding the first or last pose outside the
+    /// buffered range.
+    pub fn sample(&self, time_ms: f64) -> Option<HandJoints> {
+        let index = self
+            .packets
+            .partition_point(|p| (p.time_ms as f64) <= time_ms);
+        let (from, to) = match (index.checked_sub(1), self.packets.get(index)) {
+            (Some(before), Some(to)) => (&self.packets[before], to),
+            (Some(before), None) => return Some(self.packets[before].joints.clone()),
+            (None, Some(after)) => return Some(after.joints.clone()),
+            (None, None) => return None,
+        };
+        let span = (to.time_ms - from.time_ms) as f64;
+        let t = ((time_ms - from.time_ms as f64) / span) as f32;
+
+        let mut joints = HandJoints::default();
+        for i in 0..HAND_JOINT_COUNT {
+            let (a, b) = (from.joints.joints[i], to.joints.joints[i]);
+            joints.tracked[i] = from.joints.tracked[i] || to.joints.tracked[i];
+            joints.joints[i] = match (from.joints.tracked[i], to.joints.tracked[i]) {
+                (true, true) => HandJoint {
+                    position: a.position.lerp(b.position, t),
+                    orientation: a.orientation.slerp(b.orientation, t),
+                    radius: a.radius.lerp(b.radius, t),
+                },
+                (true, false) => a,
+                _ => b,
+            };
+        }
+        Some(joints)
+    }
+
+    fn advance(&mut self, delta: f32) {
+        self.since_last_packet += delta;
+        let Some(newest) = self.packets.back() else {
+            return;
+        };
+        let target = newest.time_ms as f64 - self.delay as f64 * 1000.0;
+        let playback = match self.playback_ms {
+            Some(playback) => playback + delta as f64 * 1000.0,
+            None => target,
+        };
+        let drift = target - playback;
+        // Follow the sender's clock, which runs at a slightly different rate than ours
+        self.playback_ms = Some(match drift.abs() > self.resync_threshold as f64 * 1000.0 {
+            true => target,
+            false => playback + drift * (1.0 - (-delta as f64).exp()),
+        });
+
+        // Keep the last packet before the playback time to interpolate from
+        let playback = self.playback_ms.unwrap_or(target);
+        while self
+            .packets
+            .get(1)
+            .is_some_and(|p| (p.time_ms as f64) <= playback)
+        {
+            self.packets.pop_front();
+        }
+    }
+}
+
+impl HandJointSource for HandPoseBuffer {
+    type Param = ();
+
+    fn write_joints(&self, _: &mut SystemParamItem<Self::Param>, joints: &mut HandJoints) {
+        let sampled = self
+            .playback_ms
+            .filter(|_| self.since_last_packet <= self.timeout)
+            .and_then(|playback| self.sample(playback));
+        let Some(sampled) = sampled else {
+            joints.tracked = [false; HAND_JOINT_COUNT];
+            return;
+        };
+        for i in 0..HAND_JOINT_COUNT {
+            joints.tracked[i] = sampled.tracked[i];
+            if sampled.tracked[i] {
+                joints.joints[i] = sampled.joints[i];
+            }
+        }
+    }
+}
+
+fn advance_hand_pose_buffers(time: Res<Time>, mut buffers: Query<&mut HandPoseBuffer>) {
+    for mut buffer in &mut buffers {
+        buffer.advance(time.delta_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(time_ms: u32) -> HandPosePacket {
+        let mut joints = HandJoints::default();
+        for (i, joint) in joints.joints.iter_mut().enumerate() {
+            let i = i as f32;
+            *joint = HandJoint {
+                position: Vec3::new(0.3 + i * 0.013, 1.2 - i * 0.004, -0.4 + i * 0.009),
+                orientation: Quat::from_euler(EulerRot::XYZ, i * 0.3, -i * 0.7, 2.0 - i * 0.2),
+                radius: 0.005 + i * 0.0007,
+            };
+        }
+        joints.tracked = [true; HAND_JOINT_COUNT];
+        joints.tracked[HandBone::LittleTip as usize] = false;
+        HandPosePacket {
+            handed: Handed::Left,
+            time_ms,
+            joints,
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let packet = packet(123_456);
+        let bytes = packet.to_bytes();
+        assert_eq!(bytes.len(), HandPosePacket::SIZE);
+        let read = HandPosePacket::from_bytes(&bytes).unwrap();
+        assert_eq!(read.handed, packet.handed);
+        assert_eq!(read.time_ms, packet.time_ms);
+        assert_eq!(read.joints.tracked, packet.joints.tracked);
+        for (a, b) in read.joints.joints.iter().zip(&packet.joints.joints) {
+            assert!(a.position.distance(b.position) <= POSITION_STEP);
+            assert!((a.radius - b.radius).abs() <= RADIUS_STEP * 0.5 + f32::EPSILON);
+            assert!(a.orientation.angle_between(b.orientation) < 0.005);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let mut bytes = packet(0).to_bytes();
+        let err = HandPosePacket::from_bytes(&bytes[..HandPosePacket::SIZE - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        bytes[0] = 2 << 4;
+        let err = HandPosePacket::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn quat_largest_component() {
+        for largest in 0..4 {
+            for sign in [1.0, -1.0] {
+                let mut q = [0.1, -0.2, 0.3, -0.15];
+                q[largest] = 0.9 * sign;
+                let q = Quat::from_array(q).normalize();
+                let bits = encode_quat(q);
+                assert_eq!((bits >> 30) as usize, largest);
+                let decoded = decode_quat(bits);
+                // The largest component is sent positive, flipping the sign of the whole quat
+                assert!(decoded.to_array()[largest] > 0.0);
+                assert!(decoded.dot(q * sign) > 0.9999);
+                assert!(decoded.angle_between(q) < 0.005);
+            }
+        }
+    }
+
+    #[test]
+    fn quat_extremes() {
+        for q in [
+            Quat::IDENTITY,
+            Quat::from_xyzw(0.5, 0.5, 0.5, 0.5),
+            Quat::from_xyzw(FRAC_1_SQRT_2, 0.0, 0.0, -FRAC_1_SQRT_2),
+        ] {
+            assert!(decode_quat(encode_quat(q)).angle_between(q) < 0.005);
+        }
+    }
+
+    #[test]
+    fn interpolates_between_packets() {
+        let mut buffer = HandPoseBuffer::default();
+        let mut to = packet(1100);
+        for joint in &mut to.joints.joints {
+            joint.position.x += 0.1;
+        }
+        buffer.push(packet(1000));
+        buffer.push(to);
+        let from = buffer.sample(1000.0).unwrap();
+        let mid = buffer.sample(1050.0).unwrap();
+        let x = from.joints[0].position.x + 0.05;
+        assert!((mid.joints[0].position.x - x).abs() < 1e-5);
+        // Untracked in both, so it isn't interpolated
+        assert!(!mid.tracked[HandBone::LittleTip as usize]);
+    }
+
+    #[test]
+    fn resyncs_when_sender_clock_restarts() {
+        let mut buffer = HandPoseBuffer::default();
+        buffer.push(packet(100_000));
+        buffer.advance(0.016);
+        assert!(buffer.playback_ms.is_some());
+
+        // Slightly late packets are dropped
+        buffer.push(packet(99_800));
+        assert_eq!(buffer.packets.len(), 1);
+
+        buffer.push(packet(50));
+        buffer.push(packet(100));
+        assert_eq!(buffer.packets.len(), 2);
+        buffer.advance(0.016);
+        let mut joints = HandJoints::default();
+        buffer.write_joints(&mut (), &mut joints);
+        assert!(joints.tracked[HandBone::Wrist as usize]);
+    }
+
+    #[test]
+    fn times_out() {
+        let mut buffer = HandPoseBuffer::default();
+        buffer.push(packet(0));
+        buffer.advance(buffer.timeout + 0.1);
+        let mut joints = HandJoints::default();
+        buffer.write_joints(&mut (), &mut joints);
+        assert!(!joints.tracked.contains(&true));
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HandBone;
+
+use super::material::HandFingertipGlow;
+use super::{Finger, HandJoints};
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandPokeSettings {
+    /// How close the fingertip surface has to get to a [`Touchable`] to hover it.
+    /// Pushing deeper than this into it also ends the hover.
+    pub hover_distance: f32,
+    /// How deep the fingertip has to push into a [`Touchable`] to press it.
+    pub press_depth: f32,
+    /// Depth below which a press is released, lower than `press_depth` to avoid flickering.
+    pub release_depth: f32,
+    /// Light up the index fingertip as it approaches a [`Touchable`].
+    pub fingertip_glow: bool,
+}
+
+impl Default for HandPokeSettings {
+    fn default() -> Self {
+        Self {
+            hover_distance: 0.05,
+            press_depth: 0.005,
+            release_depth: 0.002,
+            fingertip_glow: true,
+        }
+    }
+}
+
+/// Bounds of a [`Touchable`] in the local space of its entity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchShape {
+    Box {
+        half_size: Vec3,
+    },
+    Sphere {
+        radius: f32,
+    },
+    /// A one sided rectangle facing +Z, like the face of a button. Pokes only count from the front.
+    Plane {
+        half_size: Vec2,
+    },
+}
+
+impl TouchShape {
+    /// Distance from `point` to the surface, negative inside the shape.
+    /// Points behind a plane are negative too, as a fingertip pressing it goes through it.
+    /// `None` when the point can't poke the shape, like beside a plane.
+    pub fn signed_distance(&self, point: Vec3) -> Option<f32> {
+        match *self {
+            TouchShape::Box { half_size } => {
+                let q = point.abs() - half_size;
+                Some(q.max(Vec3::ZERO).length() + q.max_element().min(0.0))
+            }
+            TouchShape::Sphere { radius } => Some(point.length() - radius),
+            TouchShape::Plane { half_size } => {
+                let inside = point.x.abs() <= half_size.x && point.y.abs() <= half_size.y;
+                inside.then_some(point.z)
+            }
+        }
+    }
+
+    fn scaled(&self, scale: Vec3) -> Self {
+        match *self {
+            TouchShape::Box { half_size } => TouchShape::Box {
+                half_size: half_size * scale,
+            },
+            TouchShape::Sphere { radius } => TouchShape::Sphere {
+                radius: radius * scale.max_element(),
+            },
+            TouchShape::Plane { half_size } => TouchShape::Plane {
+                half_size: half_size * scale.truncate(),
+            },
+        }
+    }
+}
+
+/// Marks an entity the index fingertip can hover and press, sending [`PokeEvent`]s.
+#[derive(Component, Clone, Copy, Debug)]
+#[require(Transform)]
+pub struct Touchable {
+    pub shape: TouchShape,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PokePhase {
+    HoverStarted,
+    Pressed,
+    Released,
+    HoverEnded,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PokeEvent {
+    pub hand: Entity,
+    pub target: Entity,
+    pub phase: PokePhase,
+    /// How far the fingertip surface is pushed into the target, negative while hovering above it.
+    pub depth: f32,
+    /// World space position of the fingertip.
+    pub point: Vec3,
+}
+
+/// What the index fingertip of a hand is poking.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HandPokeState {
+    /// The closest [`Touchable`] within hover distance.
+    pub hovered: Option<Entity>,
+    pub pressed: bool,
+    /// Depth of the fingertip in the hovered target, see [`PokeEvent::depth`].
+    pub depth: f32,
+}
+
+pub(crate) fn update_hand_pokes(
+    settings: Res<HandPokeSettings>,
+    touchables: Query<(Entity, &Touchable, &GlobalTransform)>,
+    mut hands: Query<(
+        Entity,
+        &HandJoints,
+        &mut HandPokeState,
+        &mut HandFingertipGlow,
+    )>,
+    mut events: EventWriter<PokeEvent>,
+) {
+    for (hand, joints, mut state, mut glow) in &mut hands {
+        let tip = joints.get(HandBone::IndexTip);
+        let closest = match joints.is_tracked(HandBone::IndexTip) {
+            true => touchables
+                .iter()
+                .filter_map(|(e, touchable, transform)| {
+                    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+                    let local = rotation.inverse() * (tip.position - translation);
+                    let distance = touchable.shape.scaled(scale).signed_distance(local)?;
+                    let depth = tip.radius - distance;
+                    (depth.abs() <= settings.hover_distance).then_some((e, depth))
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b)),
+            false => None,
+        };
+
+        let mut send = |target, phase, depth| {
+            events.write(PokeEvent {
+                hand,
+                target,
+                phase,
+                depth,
+                point: tip.position,
+            });
+        };
+        let previous = state.hovered;
+        let previous_depth = state.depth;
+        let (hovered, depth) = closest.unzip();
+        let depth = depth.unwrap_or(-settings.hover_distance);
+        if let Some(previous) = previous.filter(|p| Some(*p) != hovered) {
+            // The new depth is measured against another target, or none
+            if state.pressed {
+                send(previous, PokePhase::Released, previous_depth);
+            }
+            send(previous, PokePhase::HoverEnded, previous_depth);
+            state.pressed = false;
+        }
+        if let Some(target) = hovered {
+            // Presses have to cross the surface while hovering, so reaching into a target from
+            // behind or inside doesn't press it
+            if previous != hovered {
+                send(target, PokePhase::HoverStarted, depth);
+            } else if !state.pressed
+                && previous_depth < settings.press_depth
+                && depth >= settings.press_depth
+            {
+                state.pressed = true;
+                send(target, PokePhase::Pressed, depth);
+            } else if state.pressed && depth < settings.release_depth {
+                state.pressed = false;
+                send(target, PokePhase::Released, depth);
+            }
+        }
+        state.hovered = hovered;
+        state.depth = depth;
+
+        if settings.fingertip_glow {
+            glow.0[Finger::Index as usize] = match hovered {
+                Some(_) => (1.0 + depth / settings.hover_distance).clamp(0.0, 1.0),
+                None => 0.0,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plane_signed_distance() {
+        let plane = TouchShape::Plane {
+            half_size: Vec2::new(0.1, 0.05),
+        };
+        assert_eq!(
+            plane.signed_distance(Vec3::new(0.05, 0.0, 0.02)),
+            Some(0.02)
+        );
+        assert_eq!(plane.signed_distance(Vec3::new(0.0, -0.05, 0.0)), Some(0.0));
+        assert_eq!(
+            plane.signed_distance(Vec3::new(0.0, 0.0, -0.01)),
+            Some(-0.01)
+        );
+        assert_eq!(plane.signed_distance(Vec3::new(0.11, 0.0, 0.02)), None);
+        assert_eq!(plane.signed_distance(Vec3::new(0.0, 0.06, -0.01)), None);
+    }
+
+    #[test]
+    fn solid_signed_distance() {
+        let sphere = TouchShape::Sphere { radius: 0.1 };
+        assert!((sphere.signed_distance(Vec3::Y * 0.3).unwrap() - 0.2).abs() < 1e-6);
+        assert!((sphere.signed_distance(Vec3::ZERO).unwrap() + 0.1).abs() < 1e-6);
+
+        let cube = TouchShape::Box {
+            half_size: Vec3::splat(0.1),
+        };
+        assert!((cube.signed_distance(Vec3::X * 0.15).unwrap() - 0.05).abs() < 1e-6);
+        assert!((cube.signed_distance(Vec3::X * 0.05).unwrap() + 0.05).abs() < 1e-6);
+        assert!((cube.signed_distance(Vec3::new(0.13, 0.14, 0.0)).unwrap() - 0.05).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,404 @@
+use bevy::prelude::*;
+use bevy_mod_xr::hands::HAND_JOINT_COUNT;
+use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use super::filter::filter_hand_joints;
+use super::source::UpdateHandJointsSet;
+use super::{HandJoint, HandJoints, HandSet, Handed};
+
+const MAGIC: [u8; 4] = *b"SKHR";
+const VERSION: u16 = 1;
+
+/// A recorded sequence of hand joint data.
+///
+/// The file format is little endian:
+/// - header: magic `SKHR`, `u16` version, `u32` frame count
+/// - frame: `f32` time in seconds, `u8` hand count, followed by each hand
+/// - hand: `u8` side (0 right, 1 left), `u32` position tracked bits, `u32` rotation tracked bits,
+///   then per joint in [`HandBone`](bevy_mod_xr::hands::HandBone) order a `[f32; 3]` position,
+///   `[f32; 4]` orientation (xyzw) and `f32` radius
+#[derive(Clone, Debug, Default)]
+pub struct HandRecording {
+    pub frames: Vec<HandRecordingFrame>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HandRecordingFrame {
+    /// Seconds since the start of the recording.
+    pub time: f32,
+    pub hands: Vec<RecordedHand>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RecordedHand {
+    pub handed: Handed,
+    pub joints: [HandJoint; HAND_JOINT_COUNT],
+    /// Bit `n` is set if joint `n` had a tracked position.
+    pub position_tracked: u32,
+    /// Bit `n` is set if joint `n` had a tracked rotation.
+    pub rotation_tracked: u32,
+}
+
+impl RecordedHand {
+    pub fn flags(&self, joint: usize) -> XrSpaceLocationFlags {
+        XrSpaceLocationFlags {
+            position_tracked: self.position_tracked & (1 << joint) != 0,
+            rotation_tracked: self.rotation_tracked & (1 << joint) != 0,
+        }
+    }
+}
+
+impl HandRecording {
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map(|f| f.time).unwrap_or(0.0)
+    }
+
+    /// The last frame recorded at or before `time`.
+    pub fn frame_at(&self, time: f32) -> Option<&HandRecordingFrame> {
+        let index = self.frames.partition_point(|f| f.time <= time);
+        self.frames.get(index.saturating_sub(1))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&(self.frames.len() as u32).to_le_bytes())?;
+        for frame in &self.frames {
+            w.write_all(&frame.time.to_le_bytes())?;
+            w.write_all(&[frame.hands.len() as u8])?;
+            for hand in &frame.hands {
+                w.write_all(&[(hand.handed == Handed::Left) as u8])?;
+                w.write_all(&hand.position_tracked.to_le_bytes())?;
+                w.write_all(&hand.rotation_tracked.to_le_bytes())?;
+                for joint in &hand.joints {
+                    let values = joint
+                        .position
+                        .to_array()
+                        .into_iter()
+                        .chain(joint.orientation.to_array())
+                        .chain([joint.radius]);
+                    for v in values {
+                        w.write_all(&v.to_le_bytes())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read(r: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a hand recording",
+            ));
+        }
+        let version = u16::from_le_bytes(read_bytes(r)?);
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported hand recording version {version}"),
+            ));
+        }
+        let frame_count = u32::from_le_bytes(read_bytes(r)?);
+        // The count comes from the file, so let truncated files fail before allocating for it
+        let mut frames = Vec::new();
+        for _ in 0..frame_count {
+            let time = f32::from_le_bytes(read_bytes(r)?);
+            let [hand_count] = read_bytes(r)?;
+            let mut hands = Vec::with_capacity(hand_count as usize);
+            for _ in 0..hand_count {
+                let [left_handed] = read_bytes(r)?;
+                let position_tracked = u32::from_le_bytes(read_bytes(r)?);
+                let rotation_tracked = u32::from_le_bytes(read_bytes(r)?);
+                let mut joints = [HandJoint::default(); HAND_JOINT_COUNT];
+                for joint in &mut joints {
+                    let mut values = [0.0; 8];
+                    for v in &mut values {
+                        *v = f32::from_le_bytes(read_bytes(r)?);
+                    }
+                    *joint = HandJoint {
+                        position: Vec3::from_slice(&values[0..3]),
+                        orientation: Quat::from_slice(&values[3..7]),
+                        radius: values[7],
+                    };
+                }
+                hands.push(RecordedHand {
+                    handed: match left_handed {
+                        0 => Handed::Right,
+                        _ => Handed::Left,
+                    },
+                    joints,
+                    position_tracked,
+                    rotation_tracked,
+                });
+            }
+            frames.push(HandRecordingFrame { time, hands });
+        }
+        Ok(Self { frames })
+    }
+}
+
+fn read_bytes<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    r.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Records hand joints to and plays them back from [`HandRecording`]s.
+pub struct HandRecordingPlugin;
+
+impl Plugin for HandRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HandRecorder>();
+        app.init_resource::<HandPlayback>();
+        // Record what the sources reported, before playback overrides it and filtering smooths it
+        app.add_systems(
+            Update,
+            (record_hands, play_hand_recording)
+                .chain()
+                .after(UpdateHandJointsSet)
+                .before(filter_hand_joints)
+                .in_set(HandSet::Joints),
+        );
+    }
+}
+
+/// Captures every hand's joints each frame while recording.
+#[derive(Resource, Default)]
+pub struct HandRecorder {
+    pub recording: bool,
+    pub clip: HandRecording,
+    start_time: Option<f32>,
+}
+
+impl HandRecorder {
+    /// Discards the current clip and starts recording a new one.
+    pub fn start(&mut self) {
+        self.recording = true;
+        self.clip = HandRecording::default();
+        self.start_time = None;
+    }
+
+    pub fn stop(&mut self) -> HandRecording {
+        self.recording = false;
+        std::mem::take(&mut self.clip)
+    }
+}
+
+/// Drives the [`HandJoints`] of the hands from a recording, overriding their sources.
+#[derive(Resource, Default)]
+pub struct HandPlayback {
+    pub clip: Option<HandRecording>,
+    pub playing: bool,
+    pub looping: bool,
+    /// Advance one recorded frame per update instead of following [`Time`],
+    /// so playback is identical regardless of the app's frame rate.
+    pub step_by_frame: bool,
+    pub time: f32,
+    frame: usize,
+}
+
+impl HandPlayback {
+    pub fn play(&mut self, clip: HandRecording) {
+        self.clip = Some(clip);
+        self.playing = true;
+        self.time = 0.0;
+        self.frame = 0;
+    }
+
+    pub fn stop(&mut self) {
+        self.playing = false;
+    }
+}
+
+fn record_hands(
+    time: Res<Time>,
+    mut recorder: ResMut<HandRecorder>,
+    hands: Query<(&HandJoints, &Handed)>,
+) {
+    if !recorder.recording {
+        return;
+    }
+    let start_time = *recorder.start_time.get_or_insert(time.elapsed_secs());
+    let mut frame = HandRecordingFrame {
+        time: time.elapsed_secs() - start_time,
+        hands: Vec::new(),
+    };
+    for (joints, handed) in &hands {
+        // Sources only report whether both the position and rotation are tracked
+        let tracked = joints
+            .tracked
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (i, tracked)| bits | (*tracked as u32) << i);
+        frame.hands.push(RecordedHand {
+            handed: *handed,
+            joints: joints.joints,
+            position_tracked: tracked,
+            rotation_tracked: tracked,
+        });
+    }
+    recorder.clip.frames.push(frame);
+}
+
+fn play_hand_recording(
+    time: Res<Time>,
+    mut playback: ResMut<HandPlayback>,
+    mut hands: Query<(&Handed, &mut HandJoints)>,
+) {
+    let playback = &mut *playback;
+    let Some(clip) = playback.clip.as_ref().filter(|_| playback.playing) else {
+        return;
+    };
+    if clip.frames.is_empty() {
+        playback.playing = false;
+        return;
+    }
+    let frame = if playback.step_by_frame {
+        if playback.frame >= clip.frames.len() {
+            playback.frame = 0;
+        }
+        let frame = &clip.frames[playback.frame];
+        playback.time = frame.time;
+        frame
+    } else {
+        if playback.time > clip.duration() {
+            playback.time = 0.0;
+        }
+        clip.frame_at(playback.time).unwrap_or(&clip.frames[0])
+    };
+
+    for (handed, mut joints) in &mut hands {
+        let hand = frame.hands.iter().find(|h| h.handed == *handed);
+        for i in 0..HAND_JOINT_COUNT {
+            let Some(hand) = hand else {
+                joints.tracked[i] = false;
+                continue;
+            };
+            let flags = hand.flags(i);
+            joints.tracked[i] = flags.position_tracked && flags.rotation_tracked;
+            // Recorded like live joints, untracked ones keep their last tracked pose
+            if joints.tracked[i] {
+                joints.joints[i] = hand.joints[i];
+            }
+        }
+    }
+
+    if playback.step_by_frame {
+        playback.frame += 1;
+    } else {
+        playback.time += time.delta_secs();
+    }
+    let finished = match playback.step_by_frame {
+        true => playback.frame >= clip.frames.len(),
+        false => playback.time > clip.duration(),
+    };
+    if finished && !playback.looping {
+        playback.playing = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip() -> HandRecording {
+        let mut joints = [HandJoint::default(); HAND_JOINT_COUNT];
+        for (i, joint) in joints.iter_mut().enumerate() {
+            *joint = HandJoint {
+                position: Vec3::new(i as f32, 1.0, -0.5),
+                orientation: Quat::from_rotation_y(i as f32 * 0.1),
+                radius: 0.01,
+            };
+        }
+        HandRecording {
+            frames: vec![
+                HandRecordingFrame {
+                    time: 0.0,
+                    hands: vec![],
+                },
+                HandRecordingFrame {
+                    time: 0.5,
+                    hands: vec![
+                        RecordedHand {
+                            handed: Handed::Left,
+                            joints,
+                            position_tracked: u32::MAX >> 6,
+                            rotation_tracked: 0b101,
+                        },
+                        RecordedHand {
+                            handed: Handed::Right,
+                            joints,
+                            position_tracked: 0,
+                            rotation_tracked: 0,
+                        },
+                    ],
+                },
+            ],
+        }
+    }
+
+    fn to_bytes(clip: &HandRecording) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        clip.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn roundtrip() {
+        let clip = clip();
+        let read = HandRecording::read(&mut to_bytes(&clip).as_slice()).unwrap();
+        assert_eq!(read.frames.len(), clip.frames.len());
+        for (a, b) in read.frames.iter().zip(&clip.frames) {
+            assert_eq!(a.time, b.time);
+            assert_eq!(a.hands.len(), b.hands.len());
+            for (a, b) in a.hands.iter().zip(&b.hands) {
+                assert_eq!(a.handed, b.handed);
+                assert_eq!(a.joints, b.joints);
+                assert_eq!(a.position_tracked, b.position_tracked);
+                assert_eq!(a.rotation_tracked, b.rotation_tracked);
+            }
+        }
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut bytes = to_bytes(&clip());
+        bytes[0] = b'X';
+        let err = HandRecording::read(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = to_bytes(&clip());
+        for len in [0, 3, 10, bytes.len() / 2, bytes.len() - 1] {
+            let err = HandRecording::read(&mut &bytes[..len]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn huge_frame_count() {
+        let mut bytes = to_bytes(&HandRecording::default());
+        bytes[6..10].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = HandRecording::read(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
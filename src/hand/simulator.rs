@@ -0,0 +1,379 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{
+    HAND_JOINT_COUNT, HandBone, LeftHand, RightHand, XrHandBoneEntities, XrHandBoneRadius,
+};
+use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+
+use super::{Finger, HandJoint, Handed};
+
+/// Finger bone lengths of the simulated right hand in meters, from metacarpal to distal.
+const FINGER_BONE_LENGTHS: [[f32; 4]; Finger::NUM] = [
+    [0.045, 0.033, 0.026, 0.0],
+    [0.065, 0.040, 0.024, 0.020],
+    [0.063, 0.045, 0.028, 0.021],
+    [0.058, 0.042, 0.027, 0.020],
+    [0.053, 0.032, 0.019, 0.018],
+];
+/// Where each finger's metacarpal starts relative to the wrist, palm down with fingers along -Z.
+const FINGER_BASES: [Vec3; Finger::NUM] = [
+    Vec3::new(-0.022, -0.012, -0.020),
+    Vec3::new(-0.020, 0.0, -0.012),
+    Vec3::new(-0.004, 0.0, -0.012),
+    Vec3::new(0.011, -0.002, -0.012),
+    Vec3::new(0.024, -0.005, -0.010),
+];
+/// Sideways angle of each finger's metacarpal, positive values lean towards the thumb.
+const FINGER_SPREAD: [f32; Finger::NUM] = [0.65, 0.08, 0.0, -0.08, -0.18];
+/// Joint radii from the metacarpal to the tip.
+const JOINT_RADII: [f32; 5] = [0.011, 0.0095, 0.0085, 0.0075, 0.0065];
+/// How far each bending joint of a finger rotates at full curl, in radians.
+const CURL_ANGLES: [f32; 3] = [1.55, 1.75, 1.2];
+const THUMB_CURL_ANGLES: [f32; 2] = [0.7, 0.8];
+
+/// A hand shape described by per-finger curl, used to synthesize a full joint set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandPose {
+    /// How curled each finger is, 0 is straight and 1 is a closed fist.
+    pub curls: [f32; Finger::NUM],
+    /// How far the thumb swings across the palm towards the index fingertip.
+    pub thumb_reach: f32,
+}
+
+impl HandPose {
+    pub const OPEN: Self = Self {
+        curls: [0.0, 0.05, 0.05, 0.08, 0.1],
+        thumb_reach: 0.0,
+    };
+    pub const PINCH: Self = Self {
+        curls: [0.3, 0.45, 0.2, 0.25, 0.3],
+        thumb_reach: 1.0,
+    };
+    pub const POINT: Self = Self {
+        curls: [0.8, 0.0, 1.0, 1.0, 1.0],
+        thumb_reach: 0.3,
+    };
+    pub const FIST: Self = Self {
+        curls: [0.8, 1.0, 1.0, 1.0, 1.0],
+        thumb_reach: 0.3,
+    };
+
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mut curls = self.curls;
+        for (curl, target) in curls.iter_mut().zip(other.curls) {
+            *curl = curl.lerp(target, t);
+        }
+        Self {
+            curls,
+            thumb_reach: self.thumb_reach.lerp(other.thumb_reach, t),
+        }
+    }
+
+    /// Generates world space joints for this pose with the wrist at `wrist`.
+    ///
+    /// The wrist's -Z axis points along the fingers and +Y out of the back of the hand.
+    pub fn joints(&self, wrist: &Transform, handed: Handed) -> [HandJoint; HAND_JOINT_COUNT] {
+        let mut joints = [HandJoint::default(); HAND_JOINT_COUNT];
+        joints[HandBone::Wrist as usize] = HandJoint {
+            position: Vec3::ZERO,
+            orientation: Quat::IDENTITY,
+            radius: 0.02,
+        };
+
+        for finger in Finger::ALL {
+            let lengths = FINGER_BONE_LENGTHS[finger as usize];
+            let (bones, angles, mut orientation) = match finger {
+                // The thumb has no intermediate bone, its chain starts at the metacarpal
+                Finger::Thumb => (
+                    &FINGER_CHAINS[0][..4],
+                    &THUMB_CURL_ANGLES[..],
+                    Quat::from_rotation_y(FINGER_SPREAD[0]) * Quat::from_rotation_z(0.8),
+                ),
+                _ => (
+                    &FINGER_CHAINS[finger as usize][..],
+                    &CURL_ANGLES[..],
+                    Quat::from_rotation_y(FINGER_SPREAD[finger as usize]),
+                ),
+            };
+            let curl = self.curls[finger as usize];
+            let mut position = FINGER_BASES[finger as usize];
+            for (i, bone) in bones.iter().enumerate() {
+                // Tips keep the orientation of the distal bone
+                if let Some(angle) = i.checked_sub(1).and_then(|i| angles.get(i)) {
+                    orientation *= Quat::from_rotation_x(-angle * curl);
+                }
+                let radius_index = match finger {
+                    Finger::Thumb => i + 1,
+                    _ => i,
+                };
+                joints[*bone as usize] = HandJoint {
+                    position,
+                    orientation,
+                    radius: JOINT_RADII[radius_index],
+                };
+                position +=
+                    orientation * Vec3::new(0.0, 0.0, -lengths.get(i).copied().unwrap_or(0.0));
+            }
+        }
+
+        // Swing the thumb around its base so the tip meets the index finger
+        if self.thumb_reach > 0.0 {
+            let base = joints[HandBone::ThumbMetacarpal as usize].position;
+            let thumb_tip = joints[HandBone::ThumbTip as usize].position;
+            let index_tip = joints[HandBone::IndexTip as usize].position;
+            let swing = Quat::IDENTITY.slerp(
+                Quat::from_rotation_arc(
+                    (thumb_tip - base).normalize(),
+                    (index_tip - base).normalize(),
+                ),
+                self.thumb_reach,
+            );
+            for bone in &FINGER_CHAINS[0][..4] {
+                let joint = &mut joints[*bone as usize];
+                joint.position = base + swing * (joint.position - base);
+                joint.orientation = swing * joint.orientation;
+            }
+        }
+
+        let middle = FINGER_CHAINS[Finger::Middle as usize];
+        joints[HandBone::Palm as usize] = HandJoint {
+            position: joints[middle[0] as usize]
+                .position
+                .lerp(joints[middle[1] as usize].position, 0.5),
+            orientation: joints[middle[0] as usize].orientation,
+            radius: 0.02,
+        };
+
+        for joint in &mut joints {
+            if handed == Handed::Left {
+                joint.position.x = -joint.position.x;
+                joint.orientation = Quat::from_xyzw(
+                    joint.orientation.x,
+                    -joint.orientation.y,
+                    -joint.orientation.z,
+                    joint.orientation.w,
+                );
+            }
+            joint.position = wrist.transform_point(joint.position);
+            joint.orientation = wrist.rotation * joint.orientation;
+        }
+        joints
+    }
+}
+
+/// Joints of each finger from its metacarpal to its tip, the thumb's last entry is unused.
+const FINGER_CHAINS: [[HandBone; 5]; Finger::NUM] = [
+    [
+        HandBone::ThumbMetacarpal,
+        HandBone::ThumbProximal,
+        HandBone::ThumbDistal,
+        HandBone::ThumbTip,
+        HandBone::ThumbTip,
+    ],
+    [
+        HandBone::IndexMetacarpal,
+        HandBone::IndexProximal,
+        HandBone::IndexIntermediate,
+        HandBone::IndexDistal,
+        HandBone::IndexTip,
+    ],
+    [
+        HandBone::MiddleMetacarpal,
+        HandBone::MiddleProximal,
+        HandBone::MiddleIntermediate,
+        HandBone::MiddleDistal,
+        HandBone::MiddleTip,
+    ],
+    [
+        HandBone::RingMetacarpal,
+        HandBone::RingProximal,
+        HandBone::RingIntermediate,
+        HandBone::RingDistal,
+        HandBone::RingTip,
+    ],
+    [
+        HandBone::LittleMetacarpal,
+        HandBone::LittleProximal,
+        HandBone::LittleIntermediate,
+        HandBone::LittleDistal,
+        HandBone::LittleTip,
+    ],
+];
+
+/// Spawns a pair of fake hands driven by mouse and keyboard, for use without an XR runtime.
+pub struct HandSimulatorPlugin;
+
+impl Plugin for HandSimulatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HandSimulatorSettings>();
+        app.add_systems(Startup, spawn_simulated_hands);
+        app.add_systems(
+            PreUpdate,
+            (control_simulated_hands, update_simulated_hands).chain(),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct HandSimulatorSettings {
+    /// Keys selecting the open, pinch, point and fist poses for the active hand.
+    pub pose_keys: [(KeyCode, HandPose); 4],
+    /// Switches which hand the mouse controls.
+    pub switch_hand_key: KeyCode,
+    /// Mouse motion only moves the active hand while this is held.
+    pub move_button: MouseButton,
+    /// Meters moved per pixel of mouse motion.
+    pub mouse_sensitivity: f32,
+    /// Meters moved per line scrolled, towards or away from the camera.
+    pub scroll_sensitivity: f32,
+    /// How quickly hands blend to a newly selected pose, in poses per second.
+    pub pose_blend_speed: f32,
+}
+
+impl Default for HandSimulatorSettings {
+    fn default() -> Self {
+        Self {
+            pose_keys: [
+                (KeyCode::Digit1, HandPose::OPEN),
+                (KeyCode::Digit2, HandPose::PINCH),
+                (KeyCode::Digit3, HandPose::POINT),
+                (KeyCode::Digit4, HandPose::FIST),
+            ],
+            switch_hand_key: KeyCode::Tab,
+            move_button: MouseButton::Right,
+            mouse_sensitivity: 0.001,
+            scroll_sensitivity: 0.02,
+            pose_blend_speed: 8.0,
+        }
+    }
+}
+
+/// State of a simulated hand, its wrist is placed relative to the camera.
+#[derive(Component, Clone, Debug)]
+pub struct SimulatedHand {
+    pub handed: Handed,
+    /// Whether mouse and keyboard input currently drive this hand.
+    pub active: bool,
+    /// Wrist position in the camera's local space.
+    pub offset: Vec3,
+    pub pose: HandPose,
+    pub target_pose: HandPose,
+}
+
+/// Spawns bone entities for a hand whose joints are synthesized rather than tracked.
+pub(crate) fn spawn_hand_bones(commands: &mut Commands, handed: Handed) -> XrHandBoneEntities {
+    let mut bones = [Entity::PLACEHOLDER; HAND_JOINT_COUNT];
+    for (bone, entity) in HandBone::get_all_bones().into_iter().zip(&mut bones) {
+        let mut cmds = commands.spawn((
+            bone,
+            XrHandBoneRadius(0.0),
+            XrSpaceLocationFlags {
+                position_tracked: true,
+                rotation_tracked: true,
+            },
+            Transform::default(),
+        ));
+        insert_hand_marker(&mut cmds, handed);
+        *entity = cmds.id();
+    }
+    XrHandBoneEntities(bones)
+}
+
+/// Inserts the `bevy_mod_xr` marker of the hand, which [`Handed`] is derived from.
+pub(crate) fn insert_hand_marker(cmds: &mut EntityCommands, handed: Handed) {
+    match handed {
+        Handed::Left => cmds.insert(LeftHand),
+        Handed::Right => cmds.insert(RightHand),
+    };
+}
+
+/// Writes synthesized joints into the bone entities of a hand.
+pub(crate) fn write_hand_bones(
+    bones: [(Mut<Transform>, Mut<GlobalTransform>, Mut<XrHandBoneRadius>); HAND_JOINT_COUNT],
+    joints: [HandJoint; HAND_JOINT_COUNT],
+) {
+    for ((mut transform, mut global, mut radius), joint) in bones.into_iter().zip(joints) {
+        *transform = Transform::from_translation(joint.position).with_rotation(joint.orientation);
+        *global = GlobalTransform::from(*transform);
+        radius.0 = joint.radius;
+    }
+}
+
+fn spawn_simulated_hands(mut commands: Commands) {
+    for handed in Handed::BOTH {
+        let bones = spawn_hand_bones(&mut commands, handed);
+        let mut cmds = commands.spawn((
+            bones,
+            SimulatedHand {
+                handed,
+                active: handed == Handed::Right,
+                offset: Vec3::new(0.12 * handed.side(), -0.15, -0.4),
+                pose: HandPose::OPEN,
+                target_pose: HandPose::OPEN,
+            },
+        ));
+        insert_hand_marker(&mut cmds, handed);
+    }
+}
+
+fn control_simulated_hands(
+    settings: Res<HandSimulatorSettings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    mut hands: Query<&mut SimulatedHand>,
+) {
+    let switch = keys.just_pressed(settings.switch_hand_key);
+    for mut hand in &mut hands {
+        if switch {
+            hand.active = !hand.active;
+        }
+        if !hand.active {
+            continue;
+        }
+        for (key, pose) in settings.pose_keys {
+            if keys.just_pressed(key) {
+                hand.target_pose = pose;
+            }
+        }
+        if buttons.pressed(settings.move_button) {
+            let delta = motion.delta * settings.mouse_sensitivity;
+            hand.offset += Vec3::new(delta.x, -delta.y, 0.0);
+        }
+        hand.offset.z -= scroll.delta.y * settings.scroll_sensitivity;
+    }
+}
+
+pub(crate) fn update_simulated_hands(
+    settings: Res<HandSimulatorSettings>,
+    time: Res<Time>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    mut hands: Query<(&mut SimulatedHand, &XrHandBoneEntities)>,
+    mut bones: Query<(&mut Transform, &mut GlobalTransform, &mut XrHandBoneRadius)>,
+) {
+    let camera = camera
+        .iter()
+        .next()
+        .map(|t| t.compute_transform())
+        .unwrap_or_default();
+    let blend = (settings.pose_blend_speed * time.delta_secs()).min(1.0);
+    for (mut hand, entities) in &mut hands {
+        hand.pose = hand.pose.lerp(&hand.target_pose, blend);
+
+        // Palm facing down and slightly inwards, fingers pointing away from the camera
+        let side = hand.handed.side();
+        let wrist = Transform {
+            translation: camera.transform_point(hand.offset),
+            rotation: camera.rotation * Quat::from_rotation_z(side * 0.4),
+            scale: Vec3::ONE,
+        };
+        let joints = hand.pose.joints(&wrist, hand.handed);
+        let Ok(bones) = bones.get_many_mut(entities.0) else {
+            warn!("Invalid Hand Joint Entities!");
+            continue;
+        };
+        write_hand_bones(bones, joints);
+    }
+}
@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::scene::SceneInstanceReady;
+use bevy_mod_xr::hands::{HAND_JOINT_COUNT, HandBone};
+
+use super::tracking::HandTrackingStatus;
+use super::{HandJoints, HandVisualSettings};
+
+/// Renders a hand with a rigged model instead of the procedural mesh.
+///
+/// Insert it on a hand entity, the model's skeleton is then driven from the hand's
+/// [`HandJoints`].
+#[derive(Component, Clone, Debug)]
+pub struct SkinnedHand {
+    /// Usually a glTF scene, loaded with `GltfAssetLabel::Scene(0).from_asset(path)`. This crate
+    /// doesn't enable bevy's `bevy_gltf` feature, the app has to enable it to load glTF hands.
+    pub scene: Handle<Scene>,
+    /// Name of the node driven by each joint, indexed by [`HandBone`].
+    /// `None` for joints the model has no bone for.
+    pub bone_names: [Option<String>; HAND_JOINT_COUNT],
+    /// Applied on top of each joint's orientation, to match the axes of the model's bones.
+    pub rotation_offsets: [Quat; HAND_JOINT_COUNT],
+    /// Move every bone to its joint, stretching the model to the size of the user's hand.
+    /// Otherwise only the root bone is moved and the others are only rotated.
+    pub match_bone_lengths: bool,
+}
+
+impl SkinnedHand {
+    /// Bones named after their [`HandBone`], like `IndexProximal`.
+    pub fn new(scene: Handle<Scene>) -> Self {
+        Self {
+            scene,
+            bone_names: HandBone::get_all_bones().map(|bone| Some(format!("{bone:?}"))),
+            rotation_offsets: [Quat::IDENTITY; HAND_JOINT_COUNT],
+            match_bone_lengths: true,
+        }
+    }
+
+    pub fn with_bone_name(mut self, bone: HandBone, name: impl Into<String>) -> Self {
+        self.bone_names[bone as usize] = Some(name.into());
+        self
+    }
+
+    /// For models without a bone for this joint.
+    pub fn without_bone(mut self, bone: HandBone) -> Self {
+        self.bone_names[bone as usize] = None;
+        self
+    }
+
+    /// Applies the same offset to every joint, for models whose bones all share an axis
+    /// convention.
+    pub fn with_rotation_offset(mut self, offset: Quat) -> Self {
+        self.rotation_offsets = [offset; HAND_JOINT_COUNT];
+        self
+    }
+
+    pub fn with_bone_rotation_offset(mut self, bone: HandBone, offset: Quat) -> Self {
+        self.rotation_offsets[bone as usize] = offset;
+        self
+    }
+}
+
+/// The scene instance of a [`SkinnedHand`].
+#[derive(Component, Clone, Copy, Debug)]
+#[relationship(relationship_target = SkinnedHandModel)]
+pub struct SkinnedHandModelOf(pub Entity);
+
+#[derive(Component, Debug)]
+#[relationship_target(relationship = SkinnedHandModelOf, linked_spawn)]
+pub struct SkinnedHandModel(Entity);
+
+impl SkinnedHandModel {
+    pub fn model(&self) -> Entity {
+        self.0
+    }
+}
+
+/// The model's bone entities, parents before their children.
+#[derive(Component)]
+pub(crate) struct SkinnedHandBones(Vec<(HandBone, Entity)>);
+
+pub(crate) fn spawn_skinned_hand(
+    trigger: Trigger<OnInsert, SkinnedHand>,
+    hands: Query<(&SkinnedHand, Option<&SkinnedHandModel>)>,
+    mut commands: Commands,
+) {
+    let hand = trigger.target();
+    let Ok((skinned, model)) = hands.get(hand) else {
+        return;
+    };
+    if let Some(model) = model {
+        commands.entity(model.0).despawn();
+    }
+    commands.spawn((
+        Name::new("Skinned Hand"),
+        SkinnedHandModelOf(hand),
+        SceneRoot(skinned.scene.clone()),
+        Transform::default(),
+    ));
+}
+
+pub(crate) fn despawn_skinned_hand(
+    trigger: Trigger<OnRemove, SkinnedHand>,
+    hands: Query<&SkinnedHandModel>,
+    mut commands: Commands,
+) {
+    if let Ok(model) = hands.get(trigger.target()) {
+        commands.entity(model.0).despawn();
+    }
+}
+
+pub(crate) fn bind_skinned_hand_bones(
+    trigger: Trigger<SceneInstanceReady>,
+    models: Query<&SkinnedHandModelOf>,
+    hands: Query<&SkinnedHand>,
+    children: Query<&Children>,
+    nodes: Query<&Name>,
+    parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let model = trigger.target();
+    let Ok(hand) = models.get(model).and_then(|model| hands.get(model.0)) else {
+        return;
+    };
+    let mut bones = Vec::new();
+    for node in children.iter_descendants(model) {
+        let Ok(name) = nodes.get(node) else {
+            continue;
+        };
+        let bone = hand
+            .bone_names
+            .iter()
+            .position(|bone_name| bone_name.as_deref() == Some(name.as_str()));
+        if let Some(bone) = bone {
+            bones.push((HandBone::get_all_bones()[bone], node));
+        }
+    }
+    if bones.is_empty() {
+        warn!("Skinned hand model has none of the configured bones");
+    }
+    // Parents have to be posed first, as their children are posed relative to them
+    bones.sort_by_key(|(_, node)| parents.iter_ancestors(*node).count());
+    commands.entity(model).insert(SkinnedHandBones(bones));
+}
+
+pub(crate) fn update_skinned_hands(
+    settings: Res<HandVisualSettings>,
+    hands: Query<(
+        &SkinnedHand,
+        &HandJoints,
+        &HandTrackingStatus,
+        Option<&HandVisualSettings>,
+    )>,
+    mut models: Query<(&SkinnedHandModelOf, &SkinnedHandBones, &mut Visibility)>,
+    parents: Query<&ChildOf>,
+    mut transforms: Query<(&mut Transform, &GlobalTransform)>,
+) {
+    for (hand, bones, mut visibility) in &mut models {
+        let Ok((skinned, joints, status, hand_settings)) = hands.get(hand.0) else {
+            continue;
+        };
+        let settings = hand_settings.unwrap_or(&settings);
+        visibility.set_if_neq(match settings.visible && status.alpha > 0.0 {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        });
+
+        // World poses of the bones posed so far this frame, as the GlobalTransforms lag behind
+        let mut posed: Vec<(Entity, GlobalTransform)> = Vec::with_capacity(bones.0.len());
+        for (i, (bone, node)) in bones.0.iter().enumerate() {
+            let joint = joints.get(*bone);
+            let parent = parents.get(*node).ok().map(ChildOf::parent);
+            let parent_transform = parent.and_then(|parent| {
+                posed
+                    .iter()
+                    .find(|(e, _)| *e == parent)
+                    .map(|(_, t)| *t)
+                    .or_else(|| transforms.get(parent).ok().map(|(_, t)| *t))
+            });
+            let parent_transform = parent_transform.unwrap_or_default();
+            let Ok((mut transform, _)) = transforms.get_mut(*node) else {
+                continue;
+            };
+
+            let (_, parent_rotation, _) = parent_transform.to_scale_rotation_translation();
+            let rotation = joint.orientation * skinned.rotation_offsets[*bone as usize];
+            transform.rotation = parent_rotation.inverse() * rotation;
+            if skinned.match_bone_lengths || i == 0 {
+                transform.translation = parent_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(joint.position);
+            }
+            posed.push((*node, parent_transform.mul_transform(*transform)));
+        }
+    }
+}
+
+/// Keeps the meshes of each model on the [`HandVisualSettings::render_layers`] of its hand.
+pub(crate) fn update_skinned_hand_render_layers(
+    settings: Res<HandVisualSettings>,
+    hands: Query<Option<&HandVisualSettings>, With<SkinnedHand>>,
+    models: Query<(Entity, &SkinnedHandModelOf), With<SkinnedHandBones>>,
+    children: Query<&Children>,
+    meshes: Query<Option<&RenderLayers>, With<Mesh3d>>,
+    mut commands: Commands,
+) {
+    for (model, hand) in &models {
+        let Ok(hand_settings) = hands.get(hand.0) else {
+            continue;
+        };
+        let layers = &hand_settings.unwrap_or(&settings).render_layers;
+        for node in children.iter_descendants(model) {
+            if meshes
+                .get(node)
+                .is_ok_and(|current| current != Some(layers))
+            {
+                commands.entity(node).insert(layers.clone());
+            }
+        }
+    }
+}
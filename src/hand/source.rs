@@ -0,0 +1,116 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::system::{StaticSystemParam, SystemParam, SystemParamItem};
+use bevy::prelude::*;
+use bevy_mod_xr::hands::{XrHandBoneEntities, XrHandBoneRadius};
+use bevy_mod_xr::spaces::XrSpaceLocationFlags;
+
+use super::filter::filter_hand_joints;
+use super::{HandJoint, HandJoints, HandSet, cleanup_hand_mesh, setup_hand_mesh};
+
+/// A component providing the joints of a hand, like the OpenXR hand tracker, a network peer or a
+/// recorded clip.
+///
+/// The [`HandPlugin`](super::HandPlugin) already adds [`XrHandBoneEntities`] and
+/// [`HandJointData`], add a [`HandJointSourcePlugin`] for each other source. Entities with the
+/// source then get [`HandJoints`], the generated mesh and the other hand features, and need a
+/// [`Handed`](super::Handed) unless it's inserted for them.
+pub trait HandJointSource: Component {
+    /// What the source reads its joints from, besides its component.
+    type Param: SystemParam + 'static;
+
+    /// Writes the latest joints and whether they're tracked.
+    /// Untracked joints should keep their last tracked pose.
+    fn write_joints(&self, param: &mut SystemParamItem<Self::Param>, joints: &mut HandJoints);
+}
+
+/// Fills [`HandJoints`] from a [`HandJointSource`] in [`HandSet::Joints`].
+pub struct HandJointSourcePlugin<S: HandJointSource>(PhantomData<S>);
+
+impl<S: HandJointSource> Default for HandJointSourcePlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: HandJointSource> Plugin for HandJointSourcePlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.register_required_components::<S, HandJoints>();
+        app.configure_sets(Update, UpdateHandJointsSet.before(filter_hand_joints));
+        app.add_observer(setup_hand_mesh::<S>);
+        app.add_observer(cleanup_hand_mesh::<S>);
+        app.add_systems(
+            Update,
+            update_hand_joints::<S>
+                .in_set(UpdateHandJointsSet)
+                .in_set(HandSet::Joints),
+        );
+    }
+}
+
+/// The systems filling [`HandJoints`] from their sources, before they're filtered.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct UpdateHandJointsSet;
+
+fn update_hand_joints<S: HandJointSource>(
+    mut hands: Query<(&S, &mut HandJoints)>,
+    mut param: StaticSystemParam<S::Param>,
+) {
+    for (source, mut joints) in &mut hands {
+        source.write_joints(&mut param, &mut joints);
+    }
+}
+
+impl HandJointSource for XrHandBoneEntities {
+    type Param = Query<
+        'static,
+        'static,
+        (
+            &'static GlobalTransform,
+            &'static XrHandBoneRadius,
+            &'static XrSpaceLocationFlags,
+        ),
+    >;
+
+    fn write_joints(&self, param: &mut SystemParamItem<Self::Param>, joints: &mut HandJoints) {
+        let Ok(entities) = param.get_many(self.0) else {
+            warn!("Invalid Hand Joint Entities!");
+            return;
+        };
+        for ((joint, tracked), (transform, radius, flags)) in joints
+            .joints
+            .iter_mut()
+            .zip(&mut joints.tracked)
+            .zip(entities)
+        {
+            *tracked = flags.position_tracked && flags.rotation_tracked;
+            // Runtimes report garbage poses for untracked joints, so hold the last valid one
+            if !*tracked {
+                continue;
+            }
+            let (_, orientation, position) = transform.to_scale_rotation_translation();
+            *joint = HandJoint {
+                position,
+                orientation,
+                radius: radius.0,
+            };
+        }
+    }
+}
+
+/// Joints set directly by the app, for tracking devices without their own [`HandJointSource`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct HandJointData(pub HandJoints);
+
+impl HandJointSource for HandJointData {
+    type Param = ();
+
+    fn write_joints(&self, _: &mut SystemParamItem<Self::Param>, joints: &mut HandJoints) {
+        for i in 0..joints.joints.len() {
+            joints.tracked[i] = self.0.tracked[i];
+            if self.0.tracked[i] {
+                joints.joints[i] = self.0.joints[i];
+            }
+        }
+    }
+}
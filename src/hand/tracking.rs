@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use super::{Finger, HandJoints, HandVisualSettings, TrackedFingers};
+
+/// How long a hand and its fingers have been untracked, and how visible its mesh is.
+///
+/// The mesh keeps showing lost fingers in their last pose for
+/// [`HandVisualSettings::tracking_grace_period`], then drops them. When the whole hand is lost
+/// it fades out instead and is hidden once fully transparent.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HandTrackingStatus {
+    /// Seconds since each finger's tip was last tracked, indexed by [`Finger`].
+    pub finger_lost_for: [f32; Finger::NUM],
+    /// Seconds since any finger of the hand was last tracked, infinite until it's first tracked.
+    pub lost_for: f32,
+    /// Opacity of the hand mesh, from 0 to 1.
+    pub alpha: f32,
+    /// Fingers shown by the mesh, the tracked ones plus those still in their grace period.
+    pub visible_fingers: TrackedFingers,
+}
+
+impl Default for HandTrackingStatus {
+    fn default() -> Self {
+        Self {
+            finger_lost_for: [f32::INFINITY; Finger::NUM],
+            lost_for: f32::INFINITY,
+            alpha: 0.0,
+            visible_fingers: TrackedFingers::empty(),
+        }
+    }
+}
+
+impl HandTrackingStatus {
+    /// Whether the hand was tracked before and isn't anymore.
+    pub fn is_lost(&self) -> bool {
+        self.lost_for > 0.0 && self.lost_for.is_finite()
+    }
+
+    /// Whether the hand hasn't been tracked since it appeared.
+    pub fn is_never_tracked(&self) -> bool {
+        self.lost_for.is_infinite()
+    }
+}
+
+pub(crate) fn update_hand_tracking_status(
+    settings: Res<HandVisualSettings>,
+    time: Res<Time>,
+    mut hands: Query<(
+        &HandJoints,
+        &mut HandTrackingStatus,
+        Option<&mut Visibility>,
+        Option<&HandVisualSettings>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (joints, mut status, visibility, hand_settings) in &mut hands {
+        let settings = hand_settings.unwrap_or(&settings);
+        let tracked = joints.tracked_fingers();
+        let grace_period = settings.tracking_grace_period;
+
+        let mut visible_fingers = TrackedFingers::empty();
+        for finger in Finger::ALL {
+            let lost_for = &mut status.finger_lost_for[finger as usize];
+            *lost_for = match tracked.contains(finger.into()) {
+                true => 0.0,
+                false => *lost_for + delta,
+            };
+            if *lost_for <= grace_period {
+                visible_fingers |= finger.into();
+            }
+        }
+        status.lost_for = match tracked.is_empty() {
+            true => status.lost_for + delta,
+            false => 0.0,
+        };
+        // Keep the last fingers while the whole hand fades out, rather than dropping them
+        if !visible_fingers.is_empty() {
+            status.visible_fingers = visible_fingers;
+        }
+
+        status.alpha = match status.lost_for <= grace_period {
+            true => fade(status.alpha, 1.0, delta, settings.fade_in_duration),
+            false => fade(status.alpha, 0.0, delta, settings.fade_out_duration),
+        };
+        if let Some(mut visibility) = visibility {
+            visibility.set_if_neq(match settings.visible && status.alpha > 0.0 {
+                true => Visibility::Inherited,
+                false => Visibility::Hidden,
+            });
+        }
+    }
+}
+
+fn fade(alpha: f32, target: f32, delta: f32, duration: f32) -> f32 {
+    if duration <= 0.0 {
+        return target;
+    }
+    let step = delta / duration;
+    match alpha < target {
+        true => (alpha + step).min(target),
+        false => (alpha - step).max(target),
+    }
+}